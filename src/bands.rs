@@ -0,0 +1,58 @@
+// Lexical frequency profile: the share of the text falling in each band of
+// a reference word list (one word per line, most frequent first; anything
+// after a comma or tab, such as a count, is ignored).
+
+use std::collections::HashMap;
+
+use crate::report::fixed;
+use crate::{AnalysisResult, Config};
+
+const BANDS: &[(usize, &str)] = &[(1_000, "top-1k"), (5_000, "top-5k"), (20_000, "top-20k")];
+
+pub struct Reference {
+    ranks: HashMap<String, usize>,
+}
+
+impl Reference {
+    pub fn parse(list: &str) -> Reference {
+        let mut ranks = HashMap::new();
+        let words = list
+            .lines()
+            .filter_map(|l| l.split([',', '\t']).next())
+            .map(|w| w.trim().to_lowercase())
+            .filter(|w| !w.is_empty());
+        for (rank, word) in words.enumerate() {
+            ranks.entry(word).or_insert(rank + 1);
+        }
+        Reference { ranks }
+    }
+
+    // Index into BANDS, or None when the word is off-list
+    fn band(&self, word: &str) -> Option<usize> {
+        let rank = *self.ranks.get(word)?;
+        BANDS.iter().position(|&(limit, _)| rank <= limit)
+    }
+}
+
+pub fn print(config: &Config, analysis: &AnalysisResult, reference: &Reference) {
+    // (tokens, types) per band, with off-list last
+    let mut counts = vec![(0u32, 0usize); BANDS.len() + 1];
+    for (word, &count) in &analysis.freq {
+        let band = reference.band(word).unwrap_or(BANDS.len());
+        counts[band].0 += count;
+        counts[band].1 += 1;
+    }
+    let total = analysis.total_words().max(1) as f64;
+
+    println!("\nFrequency bands:");
+    let labels = BANDS.iter().map(|&(_, label)| label).chain(["off-list"]);
+    for (label, (tokens, types)) in labels.zip(counts) {
+        println!(
+            "  {:<9} {:>6}% of tokens ({} tokens, {} types)",
+            label,
+            fixed(config, tokens as f64 / total * 100.0, 2),
+            tokens,
+            types
+        );
+    }
+}
@@ -0,0 +1,71 @@
+// Zipf's brevity law: frequent words tend to be short. Measured as the
+// Spearman rank correlation between each word's length and its count
+// (negative when the law holds). `--export-brevity FILE` writes the
+// scatter behind it, one row per word length.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::metadata::RunMetadata;
+use crate::{AnalysisResult, word_length};
+
+// Spearman's rho over the distinct words, or None with fewer than two or
+// when either side is constant
+pub fn correlation(analysis: &AnalysisResult) -> Option<f64> {
+    let lengths: Vec<f64> = analysis.freq.keys().map(|w| word_length(w) as f64).collect();
+    let counts: Vec<f64> = analysis.freq.values().map(|&c| c as f64).collect();
+    if lengths.len() < 2 {
+        return None;
+    }
+    pearson(&ranks(&lengths), &ranks(&counts))
+}
+
+// 1-based ranks, ties sharing their average rank
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+    let n = x.len() as f64;
+    let (mx, my) = (x.iter().sum::<f64>() / n, y.iter().sum::<f64>() / n);
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (a, b) in x.iter().zip(y) {
+        sxy += (a - mx) * (b - my);
+        sxx += (a - mx) * (a - mx);
+        syy += (b - my) * (b - my);
+    }
+    (sxx > 0.0 && syy > 0.0).then(|| sxy / (sxx * syy).sqrt())
+}
+
+// `length,words,tokens,mean_count`, shortest first
+pub fn export(path: &str, analysis: &AnalysisResult, meta: &RunMetadata) -> io::Result<()> {
+    let mut by_length: BTreeMap<usize, (usize, u64)> = BTreeMap::new();
+    for (word, &count) in &analysis.freq {
+        let entry = by_length.entry(word_length(word)).or_default();
+        entry.0 += 1;
+        entry.1 += count as u64;
+    }
+    let mut out = BufWriter::new(File::create(path)?);
+    meta.write_comments(&mut out)?;
+    writeln!(out, "length,words,tokens,mean_count")?;
+    for (length, (words, tokens)) in by_length {
+        writeln!(out, "{},{},{},{:.3}", length, words, tokens, tokens as f64 / words as f64)?;
+    }
+    out.flush()
+}
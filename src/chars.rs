@@ -0,0 +1,124 @@
+// Character analysis: letter sequences inside words and, with `--chars`,
+// the counted characters by category, which work from the frequency map
+// and so also run on loaded sessions; and alphabet coverage (`--alphabet`),
+// which reads the text for pangram sentences.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::language::Language;
+use crate::redact::shown_text;
+use crate::report::fixed;
+use crate::{AnalysisResult, Config, sentence};
+
+const SHOWN: usize = 10;
+
+// Counts every run of `n` consecutive letters within the counted words
+pub fn letter_ngrams(analysis: &AnalysisResult, n: usize) -> Vec<(String, u32)> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for (word, &count) in &analysis.freq {
+        for run in word.split(|c: char| !c.is_alphabetic()) {
+            let letters: Vec<char> = run.chars().collect();
+            for window in letters.windows(n) {
+                *counts.entry(window.iter().collect()).or_insert(0) += count;
+            }
+        }
+    }
+    let mut ranked: Vec<(String, u32)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked
+}
+
+// Counted characters as (letters, digits, punctuation, symbols and other)
+fn categories(analysis: &AnalysisResult) -> [u32; 4] {
+    let mut totals = [0; 4];
+    for (token, &count) in &analysis.freq {
+        let c = token.chars().next().unwrap_or(' ');
+        let category = if c.is_alphabetic() {
+            0
+        } else if c.is_numeric() {
+            1
+        } else if c.is_ascii_punctuation() || is_punctuation(c) {
+            2
+        } else {
+            3
+        };
+        totals[category] += count;
+    }
+    totals
+}
+
+// Unicode punctuation outside ASCII that the report tells apart from symbols
+fn is_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        '‘' | '’' | '“' | '”' | '„' | '«' | '»' | '–' | '—' | '…' | '¿' | '¡' | '·' | '「' | '」' | '、' | '。'
+    )
+}
+
+pub fn print_categories(config: &Config, analysis: &AnalysisResult) {
+    let totals = categories(analysis);
+    let all = totals.iter().sum::<u32>().max(1) as f64;
+    println!("\nCharacter categories:");
+    for (label, count) in ["Letters", "Digits", "Punctuation", "Other"].iter().zip(totals) {
+        println!("  {:<12} {} ({}%)", format!("{}:", label), count, fixed(config, count as f64 / all * 100.0, 1));
+    }
+}
+
+pub fn print_pairs(analysis: &AnalysisResult) {
+    for (n, label) in [(2, "Letter pairs"), (3, "Letter triples")] {
+        println!("\n{}:", label);
+        for (sequence, count) in letter_ngrams(analysis, n).into_iter().take(SHOWN) {
+            println!("  {} {}", sequence, count);
+        }
+    }
+}
+
+// Letters of the alphabet absent from the text, and the sentences that use
+// every letter, in text order
+pub struct Coverage {
+    pub alphabet: &'static str,
+    pub missing: Vec<char>,
+    pub pangrams: Vec<String>,
+}
+
+// The basic Latin letters plus the extra letters of `--language`
+fn alphabet(language: Option<Language>) -> &'static str {
+    match language {
+        Some(Language::Spanish) => "abcdefghijklmnñopqrstuvwxyz",
+        Some(Language::German) => "abcdefghijklmnopqrstuvwxyzäöüß",
+        _ => "abcdefghijklmnopqrstuvwxyz",
+    }
+}
+
+fn letters(text: &str) -> HashSet<char> {
+    text.chars().flat_map(char::to_lowercase).collect()
+}
+
+pub fn coverage(text: &str, config: &Config) -> Coverage {
+    let alphabet = alphabet(config.language);
+    let seen = letters(text);
+    let missing = alphabet.chars().filter(|c| !seen.contains(c)).collect();
+    let pangrams = sentence::split(text, &config.sentence_rules)
+        .into_iter()
+        .filter(|s| {
+            let seen = letters(s);
+            alphabet.chars().all(|c| seen.contains(&c))
+        })
+        .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    Coverage { alphabet, missing, pangrams }
+}
+
+pub fn print_coverage(config: &Config, coverage: &Coverage) {
+    let total = coverage.alphabet.chars().count();
+    println!("\nAlphabet coverage:");
+    println!("  Letters used: {} of {}", total - coverage.missing.len(), total);
+    if !coverage.missing.is_empty() {
+        let missing: String = coverage.missing.iter().collect();
+        println!("  Never used: {}", missing);
+    }
+    println!("  Pangram sentences: {}", coverage.pangrams.len());
+    for pangram in coverage.pangrams.iter().take(SHOWN) {
+        println!("    {}", shown_text(config.redact.as_ref(), pangram));
+    }
+}
@@ -0,0 +1,12 @@
+// ASCII bars for terminal output, scaled so the largest value fills the
+// full width. Any nonzero value gets at least one mark.
+
+pub const WIDTH: usize = 40;
+
+pub fn bar(value: u32, max: u32) -> String {
+    if value == 0 || max == 0 {
+        return String::new();
+    }
+    let marks = (value as f64 / max as f64 * WIDTH as f64).round() as usize;
+    "#".repeat(marks.max(1))
+}
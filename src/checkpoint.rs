@@ -0,0 +1,254 @@
+// Checkpointed corpus runs: `--resume STATE` counts the inputs one file at
+// a time and every `--checkpoint N` files writes the merged counts and the
+// list of finished files to STATE. Rerunning the same command after a crash
+// picks the list up and skips what is already counted.
+//
+// Layout: magic, version, the counting options and the full input list
+// of the run, finished paths, line and token offsets so far, the input hash
+// and raw line count so far, the counts in the session-file encoding, then
+// the Roman numeral and ordinal tables. A resume with other counting options
+// or other inputs is refused rather than mixing stale counts in.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+
+use crate::session::{
+    check_options, invalid, read_counts, read_str, read_u32, read_u64, write_counts, write_str, write_u32,
+    write_u64,
+};
+use crate::numerals::{self, NumeralCounts};
+use crate::{AnalysisResult, CliError, Config, FileStats, Position, metadata, pipeline, preprocess, read_text, words};
+
+const MAGIC: &[u8; 4] = b"TACK";
+const VERSION: u32 = 3;
+pub const DEFAULT_EVERY: usize = 1000;
+
+#[derive(Default)]
+struct State {
+    done: Vec<String>,
+    // Lines and raw tokens of the finished files, so positions keep counting
+    offset: Position,
+    // Of the raw lines of the finished files, as a whole-text run hashes them
+    input_hash: u64,
+    raw_lines: usize,
+    analysis: AnalysisResult,
+}
+
+pub fn run(path: &str, config: &Config) -> Result<AnalysisResult, CliError> {
+    let state_error = |e: io::Error| CliError::SessionError {
+        path: path.to_string(),
+        reason: e.to_string(),
+    };
+    let resumed = fs::exists(path).map_err(state_error)?;
+    let mut state = if resumed {
+        load(path, config).map_err(state_error)?
+    } else {
+        State {
+            analysis: AnalysisResult::with_hasher(config.hasher),
+            ..State::default()
+        }
+    };
+    let every = config.checkpoint.unwrap_or(DEFAULT_EVERY).max(1);
+    let done: HashSet<String> = state.done.iter().cloned().collect();
+
+    let mut since_save = 0;
+    for file in config.files.iter().filter(|f| !done.contains(*f)) {
+        let raw = read_text(file)?;
+        let text = preprocess::apply(&raw, config);
+        let mut part = pipeline::count(text.as_bytes(), config).map_err(|e| CliError::FileReadError {
+            path: file.clone(),
+            reason: e.to_string(),
+        })?;
+        if !config.keep_numerals {
+            part.numerals = Some(numerals::count(&text));
+        }
+        state.analysis.files.push(FileStats::new(file, text.trim().is_empty(), &part, config));
+        state.analysis.merge(part, state.offset);
+        state.input_hash = state.input_hash.wrapping_add(metadata::shift(metadata::text_hash(&raw), state.raw_lines));
+        state.raw_lines += raw.lines().count();
+        state.offset.line += text.lines().count();
+        state.offset.token += text.lines().map(|l| words(l, config).count()).sum::<usize>();
+        state.done.push(file.clone());
+        since_save += 1;
+        if since_save == every {
+            save(path, config, &state).map_err(state_error)?;
+            since_save = 0;
+        }
+    }
+    save(path, config, &state).map_err(state_error)?;
+    state.analysis.input_hash = Some(state.input_hash);
+    // The state file keeps no line structure or file entries for the files
+    // counted before
+    if resumed {
+        state.analysis.structure = None;
+        state.analysis.files.clear();
+    }
+    Ok(state.analysis)
+}
+
+fn save(path: &str, config: &Config, state: &State) -> io::Result<()> {
+    // Written aside and renamed, so a crash mid-write keeps the last state
+    let partial = format!("{}.partial", path);
+    let mut out = BufWriter::new(File::create(&partial)?);
+    out.write_all(MAGIC)?;
+    write_u32(&mut out, VERSION)?;
+    write_str(&mut out, &config.counting_options())?;
+    for list in [&config.files, &state.done] {
+        write_u64(&mut out, list.len() as u64)?;
+        for file in list {
+            write_str(&mut out, file)?;
+        }
+    }
+    write_u64(&mut out, state.offset.token as u64)?;
+    write_u64(&mut out, state.offset.line as u64)?;
+    write_u64(&mut out, state.input_hash)?;
+    write_u64(&mut out, state.raw_lines as u64)?;
+    write_counts(&mut out, &state.analysis)?;
+    let numerals = state.analysis.numerals.as_ref();
+    for table in [numerals.map(|n| &n.roman), numerals.map(|n| &n.ordinal)] {
+        let mut entries: Vec<(&String, &u32)> = table.into_iter().flatten().collect();
+        entries.sort();
+        write_u64(&mut out, entries.len() as u64)?;
+        for (word, &count) in entries {
+            write_str(&mut out, word)?;
+            write_u32(&mut out, count)?;
+        }
+    }
+    out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    fs::rename(&partial, path)
+}
+
+fn load(path: &str, config: &Config) -> io::Result<State> {
+    let bytes = fs::read(path)?;
+    let mut input = bytes.as_slice();
+    let mut magic = [0u8; 4];
+    input.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid("not a checkpoint file"));
+    }
+    let version = read_u32(&mut input)?;
+    if version != VERSION {
+        return Err(invalid(&format!("unsupported checkpoint version {}", version)));
+    }
+    check_options(&read_str(&mut input)?, config)?;
+    if read_paths(&mut input)? != config.files {
+        return Err(invalid("the inputs differ from the run that wrote this state"));
+    }
+    let done = read_paths(&mut input)?;
+    let token = read_u64(&mut input)? as usize;
+    let line = read_u64(&mut input)? as usize;
+    let input_hash = read_u64(&mut input)?;
+    let raw_lines = read_u64(&mut input)? as usize;
+    let mut analysis = read_counts(&mut input, config.hasher)?;
+    let roman = read_table(&mut input)?;
+    let ordinal = read_table(&mut input)?;
+    if !config.keep_numerals {
+        analysis.numerals = Some(NumeralCounts { roman, ordinal });
+    }
+    Ok(State { done, offset: Position { token, line }, input_hash, raw_lines, analysis })
+}
+
+fn read_table(input: &mut &[u8]) -> io::Result<HashMap<String, u32>> {
+    let len = read_u64(input)? as usize;
+    // Each entry takes at least its 4-byte length and its count
+    if len > input.len() / 8 {
+        return Err(invalid("numeral count exceeds the file size"));
+    }
+    (0..len).map(|_| Ok((read_str(input)?, read_u32(input)?))).collect()
+}
+
+fn read_paths(input: &mut &[u8]) -> io::Result<Vec<String>> {
+    let len = read_u64(input)? as usize;
+    // Each path takes at least its 4-byte length
+    if len > input.len() / 4 {
+        return Err(invalid("path count exceeds the file size"));
+    }
+    (0..len).map(|_| read_str(input)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("text_analyzer-{}-{}", std::process::id(), name));
+        path.to_string_lossy().into_owned()
+    }
+
+    fn state(config: &Config) -> State {
+        let mut analysis = AnalysisResult::with_hasher(config.hasher);
+        analysis.freq.insert("word".to_string(), 3);
+        State {
+            done: vec!["a.txt".to_string()],
+            offset: Position { token: 3, line: 2 },
+            analysis,
+            ..State::default()
+        }
+    }
+
+    #[test]
+    fn a_saved_state_loads_back() {
+        let config = Config { files: vec!["a.txt".to_string(), "b.txt".to_string()], ..Config::default() };
+        let path = scratch("state.tack");
+        save(&path, &config, &state(&config)).unwrap();
+        let loaded = load(&path, &config).unwrap();
+        assert_eq!(loaded.done, ["a.txt"]);
+        assert_eq!(loaded.offset, Position { token: 3, line: 2 });
+        assert_eq!(loaded.analysis.freq["word"], 3);
+
+        let bytes = fs::read(&path).unwrap();
+        for len in [0, 6, 20, bytes.len() - 1] {
+            fs::write(&path, &bytes[..len]).unwrap();
+            assert!(load(&path, &config).is_err(), "read {} of {} bytes", len, bytes.len());
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn numerals_and_the_input_hash_are_kept() {
+        let a = scratch("numerals-a.txt");
+        fs::write(&a, "CHAPTER IV\nthe 1st day\n").unwrap();
+        let path = scratch("numerals.tack");
+        let config = Config { files: vec![a.clone()], ..Config::default() };
+        let first = run(&path, &config).unwrap();
+        let again = run(&path, &config).unwrap();
+        for file in [&a, &path] {
+            fs::remove_file(file).unwrap();
+        }
+        assert_eq!(first.input_hash, Some(metadata::text_hash("CHAPTER IV\nthe 1st day\n")));
+        assert_eq!(again.input_hash, first.input_hash);
+        let numerals = again.numerals.unwrap();
+        assert_eq!((numerals.roman["iv"], numerals.ordinal["1st"]), (1, 1));
+    }
+
+    #[test]
+    fn a_state_for_other_inputs_is_refused() {
+        let config = Config { files: vec!["a.txt".to_string()], ..Config::default() };
+        let path = scratch("other-inputs.tack");
+        save(&path, &config, &state(&config)).unwrap();
+        let other = Config { files: vec!["a.txt".to_string(), "c.txt".to_string()], ..Config::default() };
+        let error = load(&path, &other).err().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(error.to_string(), "the inputs differ from the run that wrote this state");
+    }
+
+    #[test]
+    fn a_rerun_skips_finished_files() {
+        let (a, b) = (scratch("resume-a.txt"), scratch("resume-b.txt"));
+        fs::write(&a, "one two\n").unwrap();
+        fs::write(&b, "two three\n").unwrap();
+        let path = scratch("resume.tack");
+        let config = Config { files: vec![a.clone(), b.clone()], checkpoint: Some(1), ..Config::default() };
+
+        let first = run(&path, &config).unwrap();
+        assert_eq!((first.freq["two"], first.files.len()), (2, 2));
+        let again = run(&path, &config).unwrap();
+        assert_eq!((again.freq["two"], again.total_words()), (2, 4));
+        assert!(again.files.is_empty());
+        assert_eq!(again.input_hash, first.input_hash);
+        for file in [&a, &b, &path] {
+            fs::remove_file(file).unwrap();
+        }
+    }
+}
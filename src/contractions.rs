@@ -0,0 +1,103 @@
+// Contraction expansion ("don't" -> "do not") from a built-in table, so
+// function-word counts and readability see the expanded forms.
+
+// Kept sorted for binary search
+const CONTRACTIONS: &[(&str, &str)] = &[
+    ("ain't", "am not"),
+    ("aren't", "are not"),
+    ("can't", "cannot"),
+    ("couldn't", "could not"),
+    ("didn't", "did not"),
+    ("doesn't", "does not"),
+    ("don't", "do not"),
+    ("hadn't", "had not"),
+    ("hasn't", "has not"),
+    ("haven't", "have not"),
+    ("he'd", "he would"),
+    ("he'll", "he will"),
+    ("he's", "he is"),
+    ("here's", "here is"),
+    ("how's", "how is"),
+    ("i'd", "i would"),
+    ("i'll", "i will"),
+    ("i'm", "i am"),
+    ("i've", "i have"),
+    ("isn't", "is not"),
+    ("it'd", "it would"),
+    ("it'll", "it will"),
+    ("it's", "it is"),
+    ("let's", "let us"),
+    ("mightn't", "might not"),
+    ("mustn't", "must not"),
+    ("needn't", "need not"),
+    ("shan't", "shall not"),
+    ("she'd", "she would"),
+    ("she'll", "she will"),
+    ("she's", "she is"),
+    ("shouldn't", "should not"),
+    ("that's", "that is"),
+    ("there's", "there is"),
+    ("they'd", "they would"),
+    ("they'll", "they will"),
+    ("they're", "they are"),
+    ("they've", "they have"),
+    ("wasn't", "was not"),
+    ("we'd", "we would"),
+    ("we'll", "we will"),
+    ("we're", "we are"),
+    ("we've", "we have"),
+    ("weren't", "were not"),
+    ("what's", "what is"),
+    ("where's", "where is"),
+    ("who's", "who is"),
+    ("won't", "will not"),
+    ("wouldn't", "would not"),
+    ("y'all", "you all"),
+    ("you'd", "you would"),
+    ("you'll", "you will"),
+    ("you're", "you are"),
+    ("you've", "you have"),
+];
+
+fn expansion(word: &str) -> Option<&'static str> {
+    CONTRACTIONS
+        .binary_search_by(|(c, _)| (*c).cmp(word))
+        .ok()
+        .map(|i| CONTRACTIONS[i].1)
+}
+
+// Rewrites each whitespace-separated token, keeping the separators and any
+// surrounding punctuation, and the capital of a sentence-initial "Don't"
+pub fn expand(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + text.len() / 8);
+    let mut rest = text;
+    while !rest.is_empty() {
+        let word_start = rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len());
+        out.push_str(&rest[..word_start]);
+        rest = &rest[word_start..];
+        let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (token, after) = rest.split_at(word_end);
+        rest = after;
+
+        let core_start = token.find(char::is_alphabetic).unwrap_or(token.len());
+        let core_end = token
+            .rfind(char::is_alphabetic)
+            .map_or(core_start, |i| i + token[i..].chars().next().map_or(0, char::len_utf8));
+        let core = &token[core_start..core_end.max(core_start)];
+        match expansion(&core.to_lowercase()) {
+            Some(expanded) => {
+                out.push_str(&token[..core_start]);
+                if core.starts_with(char::is_uppercase) {
+                    let mut chars = expanded.chars();
+                    out.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+                    out.push_str(chars.as_str());
+                } else {
+                    out.push_str(expanded);
+                }
+                out.push_str(&token[core_end..]);
+            }
+            None => out.push_str(token),
+        }
+    }
+    out
+}
@@ -0,0 +1,58 @@
+// Word-by-word co-occurrence matrix for the top words, exported as CSV for
+// embedding and network-analysis tools. Two counted tokens co-occur when
+// they are at most `window` positions apart.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::csv::write_field;
+use crate::redact::shown;
+use crate::{AnalysisResult, Config, tokens};
+
+const DEFAULT_WINDOW: usize = 5;
+const DEFAULT_TOP: usize = 200;
+
+pub fn export(path: &str, text: &str, config: &Config, analysis: &AnalysisResult) -> io::Result<()> {
+    let window = config.cooccur_window.unwrap_or(DEFAULT_WINDOW);
+    let top: Vec<&String> = analysis
+        .ranked()
+        .into_iter()
+        .take(config.top.unwrap_or(DEFAULT_TOP))
+        .map(|(word, _)| word)
+        .collect();
+    let index: HashMap<&str, usize> = top.iter().enumerate().map(|(i, w)| (w.as_str(), i)).collect();
+
+    // Only top words matter, so the stream is reduced to their indices
+    let stream: Vec<Option<usize>> = tokens(text, config)
+        .map(|(_, word)| index.get(word.as_str()).copied())
+        .collect();
+    let mut matrix = vec![vec![0u32; top.len()]; top.len()];
+    for (i, a) in stream.iter().enumerate() {
+        let Some(a) = *a else { continue };
+        for b in stream.iter().skip(i + 1).take(window).flatten() {
+            matrix[a][*b] += 1;
+            if a != *b {
+                matrix[*b][a] += 1;
+            }
+        }
+    }
+
+    let labels: Vec<Cow<str>> = top.iter().map(|w| shown(config.redact.as_ref(), w)).collect();
+    let mut out = BufWriter::new(File::create(path)?);
+    write!(out, "word")?;
+    for label in &labels {
+        write!(out, ",")?;
+        write_field(&mut out, label)?;
+    }
+    writeln!(out)?;
+    for (label, row) in labels.iter().zip(&matrix) {
+        write_field(&mut out, label)?;
+        for count in row {
+            write!(out, ",{}", count)?;
+        }
+        writeln!(out)?;
+    }
+    out.flush()
+}
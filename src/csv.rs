@@ -0,0 +1,28 @@
+// CSV helpers shared by the exporters
+
+use std::io::{self, Write};
+
+// Quotes a field when it holds a separator, quote or line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Writes the field straight to `out`, quoting like `csv_field` without
+// building a String per row
+pub fn write_field(out: &mut impl Write, value: &str) -> io::Result<()> {
+    if !value.contains([',', '"', '\n', '\r']) {
+        return out.write_all(value.as_bytes());
+    }
+    out.write_all(b"\"")?;
+    for (i, part) in value.split('"').enumerate() {
+        if i > 0 {
+            out.write_all(b"\"\"")?;
+        }
+        out.write_all(part.as_bytes())?;
+    }
+    out.write_all(b"\"")
+}
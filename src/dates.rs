@@ -0,0 +1,171 @@
+// Date and timestamp tokens: ISO dates (2024-01-05, 2024/01/05), ISO
+// timestamps (2024-01-05T10:00:00Z), Apache log stamps (05/Jan/2024:10:00:00)
+// and a date token followed by a separate time token (2024-01-05 10:00).
+
+use std::fmt;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    year: u32,
+    month: u32,
+    day: u32,
+    time: Option<(u32, u32, u32)>,
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)?;
+        if let Some((h, m, s)) = self.time {
+            write!(f, " {:02}:{:02}:{:02}", h, m, s)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct DateStats {
+    pub count: usize,
+    pub earliest: Option<DateTime>,
+    pub latest: Option<DateTime>,
+}
+
+pub fn extract(text: &str) -> DateStats {
+    let mut stats = DateStats { count: 0, earliest: None, latest: None };
+    let mut tokens = text.split_whitespace().map(trim).peekable();
+    while let Some(token) = tokens.next() {
+        let Some(mut date) = parse_token(token) else {
+            continue;
+        };
+        if date.time.is_none()
+            && let Some(time) = tokens.peek().and_then(|t| parse_time(t))
+        {
+            date.time = Some(time);
+            tokens.next();
+        }
+        stats.count += 1;
+        stats.earliest = Some(stats.earliest.map_or(date, |e| e.min(date)));
+        stats.latest = Some(stats.latest.map_or(date, |l| l.max(date)));
+    }
+    stats
+}
+
+fn trim(token: &str) -> &str {
+    token
+        .trim_start_matches(['[', '(', '"', '\''])
+        .trim_end_matches([']', ')', '"', '\'', ',', ';', '.'])
+}
+
+fn parse_token(token: &str) -> Option<DateTime> {
+    // ISO with an optional "T" time part
+    let (date_part, time_part) = match token.split_once('T') {
+        Some((d, t)) => (d, Some(t)),
+        None => (token, None),
+    };
+    if let Some(mut date) = parse_iso_date(date_part) {
+        if let Some(t) = time_part {
+            date.time = Some(parse_time(t)?);
+        }
+        return Some(date);
+    }
+    parse_apache(token)
+}
+
+fn parse_iso_date(s: &str) -> Option<DateTime> {
+    let separator = if s.contains('-') { '-' } else { '/' };
+    let mut parts = s.split(separator);
+    let year = parts.next().filter(|y| y.len() == 4)?;
+    let month = parts.next().filter(|m| m.len() == 2)?;
+    let day = parts.next().filter(|d| d.len() == 2)?;
+    if parts.next().is_some() {
+        return None;
+    }
+    date(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+}
+
+// 05/Jan/2024:10:00:00 (the zone, a separate token, is ignored)
+fn parse_apache(s: &str) -> Option<DateTime> {
+    let mut parts = s.splitn(3, '/');
+    let day = parts.next().filter(|d| d.len() == 2)?.parse().ok()?;
+    let month_name = parts.next()?.to_ascii_lowercase();
+    let month = MONTHS.iter().position(|&m| m == month_name)? as u32 + 1;
+    let rest = parts.next()?;
+    let (year, time) = match rest.split_once(':') {
+        Some((y, t)) => (y, Some(parse_time(t)?)),
+        None => (rest, None),
+    };
+    let mut date = date(year.parse().ok()?, month, day)?;
+    date.time = time;
+    Some(date)
+}
+
+fn date(year: u32, month: u32, day: u32) -> Option<DateTime> {
+    let valid = (1..=12).contains(&month) && (1..=31).contains(&day);
+    valid.then_some(DateTime { year, month, day, time: None })
+}
+
+// HH:MM or HH:MM:SS, ignoring fractional seconds and zone suffixes
+fn parse_time(s: &str) -> Option<(u32, u32, u32)> {
+    let s = s.split(['.', 'Z', '+']).next()?;
+    let mut parts = s.split(':');
+    let hour: u32 = parts.next().filter(|h| h.len() == 2)?.parse().ok()?;
+    let minute: u32 = parts.next().filter(|m| m.len() == 2)?.parse().ok()?;
+    let second: u32 = match parts.next() {
+        Some(sec) if sec.len() == 2 => sec.parse().ok()?,
+        Some(_) => return None,
+        None => 0,
+    };
+    if parts.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    Some((hour, minute, second))
+}
+
+pub fn print(stats: &DateStats) {
+    println!("\nDates:");
+    println!("  Date/time tokens: {}", stats.count);
+    if let (Some(earliest), Some(latest)) = (stats.earliest, stats.latest) {
+        println!("  Time range: {} to {}", earliest, latest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(text: &str) -> (usize, Option<String>, Option<String>) {
+        let stats = extract(text);
+        (stats.count, stats.earliest.map(|d| d.to_string()), stats.latest.map(|d| d.to_string()))
+    }
+
+    #[test]
+    fn finds_iso_dates_and_timestamps() {
+        let (count, earliest, latest) = range("on 2024/03/01, then 2024-01-05T10:00:00.25Z and (2023-12-31).");
+        assert_eq!(count, 3);
+        assert_eq!(earliest.as_deref(), Some("2023-12-31"));
+        assert_eq!(latest.as_deref(), Some("2024-03-01"));
+        assert_eq!(range("2024-01-05T10:00:00Z").1.as_deref(), Some("2024-01-05 10:00:00"));
+    }
+
+    #[test]
+    fn reads_apache_log_stamps() {
+        let line = r#"127.0.0.1 - - [05/Jan/2024:10:00:07 +0000] "GET / HTTP/1.1" 200"#;
+        assert_eq!(range(line), (1, Some("2024-01-05 10:00:07".to_string()), Some("2024-01-05 10:00:07".to_string())));
+    }
+
+    #[test]
+    fn a_following_time_token_joins_the_date() {
+        let (count, earliest, _) = range("2024-01-05 09:30 started");
+        assert_eq!(count, 1);
+        assert_eq!(earliest.as_deref(), Some("2024-01-05 09:30:00"));
+    }
+
+    #[test]
+    fn rejects_what_only_looks_like_a_date() {
+        for text in ["2024-13-01", "2024-01-32", "24-01-05", "2024-01-05-07", "2024-01-05T25:00", "05/Foo/2024", "1/2/2024"] {
+            assert_eq!(extract(text).count, 0, "{}", text);
+        }
+    }
+}
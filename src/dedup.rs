@@ -0,0 +1,119 @@
+// `--dedup-docs THRESHOLD`: near-duplicate inputs are found before counting
+// and left out of it, so boilerplate copies do not skew corpus statistics.
+// Each input becomes the set of its five-word shingles, summarized by a
+// MinHash signature whose agreement estimates the Jaccard similarity of two
+// sets. Only inputs sharing a band of their signatures are compared. An
+// input at least THRESHOLD similar to an earlier kept one is a duplicate of
+// it; the first of each group is kept, in the order given.
+
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::{Config, metadata, plain_words};
+
+const SHINGLE_WORDS: usize = 5;
+const HASHES: usize = 128;
+
+type Signature = [u64; HASHES];
+
+// splitmix64's finalizer, one hash function per seed
+fn mix(hash: u64, seed: usize) -> u64 {
+    let mut z = hash.wrapping_add((seed as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// None for a text without words; a text shorter than a shingle is one
+fn signature(text: &str, config: &Config) -> Option<Signature> {
+    let words = plain_words(text, config);
+    if words.is_empty() {
+        return None;
+    }
+    let mut signature = [u64::MAX; HASHES];
+    for shingle in words.windows(SHINGLE_WORDS.min(words.len())) {
+        let hash = metadata::fnv1a(shingle.join(" ").as_bytes());
+        for (seed, min) in signature.iter_mut().enumerate() {
+            *min = (*min).min(mix(hash, seed));
+        }
+    }
+    Some(signature)
+}
+
+fn similarity(a: &Signature, b: &Signature) -> f64 {
+    a.iter().zip(b).filter(|(x, y)| x == y).count() as f64 / HASHES as f64
+}
+
+// Signature values per band: the most that still puts two inputs at the
+// threshold into a shared band with 95% probability
+fn band_rows(threshold: f64) -> usize {
+    [16, 8, 4, 2]
+        .into_iter()
+        .find(|&rows| 1.0 - (1.0 - threshold.powi(rows as i32)).powi((HASHES / rows) as i32) >= 0.95)
+        .unwrap_or(1)
+}
+
+// For each text, the earlier text it duplicates and their similarity
+pub fn find(texts: &[&str], config: &Config, threshold: f64) -> Vec<Option<(usize, f64)>> {
+    let signatures: Vec<Option<Signature>> = texts.par_iter().map(|text| signature(text, config)).collect();
+    let rows = band_rows(threshold);
+    let mut buckets: HashMap<(usize, &[u64]), Vec<usize>> = HashMap::new();
+    let mut found = vec![None; texts.len()];
+    for (index, signature) in signatures.iter().enumerate() {
+        let Some(signature) = signature else {
+            continue;
+        };
+        let bands: Vec<(usize, &[u64])> = signature.chunks(rows).enumerate().collect();
+        let mut best: Option<(usize, f64)> = None;
+        for band in &bands {
+            for &kept in buckets.get(band).into_iter().flatten() {
+                let Some(other) = &signatures[kept] else {
+                    continue;
+                };
+                let s = similarity(signature, other);
+                if s >= threshold && best.is_none_or(|(b, bs)| s > bs || (s == bs && kept < b)) {
+                    best = Some((kept, s));
+                }
+            }
+        }
+        match best {
+            Some(duplicate) => found[index] = Some(duplicate),
+            None => {
+                for band in bands {
+                    buckets.entry(band).or_default().push(index);
+                }
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "the quick brown fox jumps over the lazy dog while the small cat \
+        sleeps on the warm mat beside the old door and the bird sings a song";
+
+    #[test]
+    fn copies_are_duplicates_of_the_first() {
+        let copy = TEXT.to_uppercase();
+        let other = "an entirely different text about rivers mountains and the sea at night";
+        let found = find(&[TEXT, other, &copy, TEXT], &Config::default(), 0.9);
+        assert_eq!(found, [None, None, Some((0, 1.0)), Some((0, 1.0))]);
+    }
+
+    #[test]
+    fn a_small_edit_stays_above_a_loose_threshold() {
+        let edited = TEXT.replace("song", "tune");
+        let found = find(&[TEXT, &edited], &Config::default(), 0.7);
+        assert!(found[1].is_some_and(|(of, s)| of == 0 && s < 1.0));
+        assert_eq!(find(&[TEXT, &edited], &Config::default(), 1.0)[1], None);
+    }
+
+    #[test]
+    fn texts_without_words_are_never_duplicates() {
+        assert_eq!(find(&["", " \n"], &Config::default(), 0.5), [None, None]);
+    }
+}
@@ -0,0 +1,29 @@
+// `text_analyzer demo`: analyze an embedded sample and print every report
+// format, so new users can see the output and packagers can smoke-test.
+
+use std::time::SystemTime;
+
+use crate::metadata::{self, RunMetadata};
+use crate::{Config, analyze, print_json, print_report};
+
+const SAMPLE_TEXT: &str = "\
+The quick brown fox jumps over the lazy dog. The dog sleeps, and the fox
+runs into the forest. Foxes are quick; dogs are loyal. A curious reader
+might count the words in this sample, and the analyzer does exactly that.
+";
+
+pub fn run() {
+    let config = Config {
+        file_path: "<demo sample>".to_string(),
+        ..Config::default()
+    };
+    let started = SystemTime::now();
+    let mut analysis = analyze(SAMPLE_TEXT, &config);
+    analysis.input_hash = Some(metadata::text_hash(SAMPLE_TEXT));
+
+    println!("--- Format: text ---");
+    print_report(&config, &analysis);
+
+    println!("\n--- Format: json ---");
+    print_json(&config, &analysis, &RunMetadata::finish(&config, &analysis, started));
+}
@@ -0,0 +1,64 @@
+// Lexical diversity, `--stat diversity`: the type-token ratio, its moving
+// average over fixed windows (MATTR, which unlike plain TTR does not fall
+// as texts grow longer), and the vocabulary richness measures of Guiraud
+// (types over the root of tokens), Herdan (log types over log tokens) and
+// Yule (K, the chance two random tokens are the same word, times 10^4).
+// MATTR needs the tokens in order, so this stat reads the text whole.
+
+use std::collections::HashMap;
+
+use crate::{AnalysisResult, Config, tokens};
+
+// Tokens per MATTR window
+pub const WINDOW: usize = 100;
+
+pub struct Diversity {
+    pub ttr: f64,
+    pub mattr: Option<f64>,
+    pub guiraud: f64,
+    // Undefined below two tokens
+    pub herdan: Option<f64>,
+    pub yule_k: f64,
+}
+
+// Mean TTR of every WINDOW-token window; the plain TTR for shorter texts
+pub fn mattr(text: &str, config: &Config) -> Option<f64> {
+    let words: Vec<String> = tokens(text, config).map(|(_, w)| w).collect();
+    if words.is_empty() {
+        return None;
+    }
+    let window = WINDOW.min(words.len());
+    let mut counts: HashMap<&str, u32> = HashMap::new();
+    for word in &words[..window] {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    let mut sum = counts.len() as f64;
+    for (leaving, entering) in words.iter().zip(&words[window..]) {
+        if let Some(count) = counts.get_mut(leaving.as_str()) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(leaving.as_str());
+            }
+        }
+        *counts.entry(entering).or_insert(0) += 1;
+        sum += counts.len() as f64;
+    }
+    let windows = words.len() - window + 1;
+    Some(sum / windows as f64 / window as f64)
+}
+
+pub fn measure(analysis: &AnalysisResult) -> Option<Diversity> {
+    let tokens = analysis.total_words() as f64;
+    let types = analysis.freq.len() as f64;
+    if tokens == 0.0 {
+        return None;
+    }
+    let squares: f64 = analysis.freq.values().map(|&c| c as f64 * c as f64).sum();
+    Some(Diversity {
+        ttr: types / tokens,
+        mattr: analysis.mattr,
+        guiraud: types / tokens.sqrt(),
+        herdan: (tokens > 1.0).then(|| types.ln() / tokens.ln()),
+        yule_k: 10_000.0 * (squares - tokens) / (tokens * tokens),
+    })
+}
@@ -0,0 +1,126 @@
+// Input encodings. Each file is sniffed on its own from its first bytes: a
+// byte order mark decides outright, NUL bytes on alternating positions mark
+// UTF-16 without one, and bytes that are not UTF-8 fall back to
+// Windows-1252 (a superset of Latin-1). Anything else is read as UTF-8.
+
+use std::io::{self, Read};
+
+use flate2::read::MultiGzDecoder;
+
+// Bytes looked at to pick an encoding
+pub const SNIFF_BYTES: usize = 8000;
+
+// Share of NUL bytes in one half of the positions that marks UTF-16
+const UTF16_NULS: f64 = 0.3;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Windows1252,
+}
+
+impl Encoding {
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf8Bom => "UTF-8 (BOM)",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Windows1252 => "Windows-1252",
+        }
+    }
+
+    // Length of the byte order mark the text starts with
+    pub fn bom_len(self, head: &[u8]) -> usize {
+        match self {
+            Encoding::Utf8Bom => 3,
+            Encoding::Utf16Le if head.starts_with(&[0xff, 0xfe]) => 2,
+            Encoding::Utf16Be if head.starts_with(&[0xfe, 0xff]) => 2,
+            _ => 0,
+        }
+    }
+}
+
+// Picks the encoding from the first bytes of a (decompressed) input
+pub fn detect(head: &[u8]) -> Encoding {
+    let head = &head[..head.len().min(SNIFF_BYTES)];
+    if head.starts_with(&[0xef, 0xbb, 0xbf]) {
+        return Encoding::Utf8Bom;
+    }
+    if head.starts_with(&[0xff, 0xfe]) {
+        return Encoding::Utf16Le;
+    }
+    if head.starts_with(&[0xfe, 0xff]) {
+        return Encoding::Utf16Be;
+    }
+    let pairs = head.len() / 2;
+    if pairs > 0 {
+        let nuls = |offset: usize| head.iter().skip(offset).step_by(2).filter(|&&b| b == 0).count();
+        let (even, odd) = (nuls(0) as f64 / pairs as f64, nuls(1) as f64 / pairs as f64);
+        if odd >= UTF16_NULS && even < UTF16_NULS / 3.0 {
+            return Encoding::Utf16Le;
+        }
+        if even >= UTF16_NULS && odd < UTF16_NULS / 3.0 {
+            return Encoding::Utf16Be;
+        }
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => Encoding::Utf8,
+        // Cut mid-character at the end of the sniffed bytes
+        Err(e) if e.error_len().is_none() => Encoding::Utf8,
+        Err(_) => Encoding::Windows1252,
+    }
+}
+
+// Decodes a whole input in the encoding its head shows
+pub fn decode(mut bytes: Vec<u8>) -> io::Result<String> {
+    let encoding = detect(&bytes);
+    let bom = encoding.bom_len(&bytes);
+    match encoding {
+        Encoding::Utf8 | Encoding::Utf8Bom => {
+            bytes.drain(..bom);
+            String::from_utf8(bytes).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+            })
+        }
+        Encoding::Utf16Le => utf16(&bytes[bom..], u16::from_le_bytes),
+        Encoding::Utf16Be => utf16(&bytes[bom..], u16::from_be_bytes),
+        Encoding::Windows1252 => Ok(bytes.iter().map(|&b| windows_1252(b)).collect()),
+    }
+}
+
+// The encoding of the file at `path`, looking past gzip as reading does
+pub fn sniff(path: &str) -> io::Result<Encoding> {
+    let mut head = Vec::with_capacity(SNIFF_BYTES);
+    std::fs::File::open(path)?.take(SNIFF_BYTES as u64).read_to_end(&mut head)?;
+    if head.starts_with(&GZIP_MAGIC) {
+        let file = std::fs::File::open(path)?;
+        head.clear();
+        MultiGzDecoder::new(file).take(SNIFF_BYTES as u64).read_to_end(&mut head)?;
+    }
+    Ok(detect(&head))
+}
+
+fn utf16(body: &[u8], unit: fn([u8; 2]) -> u16) -> io::Result<String> {
+    let units = body.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-16"))
+}
+
+// Bytes 0x80-0x9F hold punctuation in Windows-1252; the rest match Latin-1
+fn windows_1252(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+        '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+    ];
+    match byte {
+        0x80..=0x9f => HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
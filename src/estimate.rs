@@ -0,0 +1,60 @@
+// Rough LLM token budgets for a text. These are approximations, not any
+// particular tokenizer: a chars/4 rule of thumb, the common 4/3 tokens per
+// word ratio, and a word-piece estimate that splits tokens into letter
+// runs, digit groups and symbols.
+
+pub struct TokenEstimate {
+    pub chars: usize,
+    pub words: usize,
+    pub word_pieces: usize,
+}
+
+impl TokenEstimate {
+    pub fn by_chars(&self) -> usize {
+        self.chars.div_ceil(4)
+    }
+
+    pub fn by_words(&self) -> usize {
+        (self.words * 4).div_ceil(3)
+    }
+}
+
+pub fn estimate(text: &str) -> TokenEstimate {
+    TokenEstimate {
+        chars: text.chars().count(),
+        words: text.split_whitespace().count(),
+        word_pieces: text.split_whitespace().map(word_pieces).sum(),
+    }
+}
+
+fn word_pieces(word: &str) -> usize {
+    let mut pieces = 0;
+    let mut chars = word.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_alphabetic() {
+            let mut len: usize = 1;
+            while chars.next_if(|c| c.is_ascii_alphabetic()).is_some() {
+                len += 1;
+            }
+            // Common words are one piece; long ones split every ~5 letters
+            pieces += 1 + len.saturating_sub(7).div_ceil(5);
+        } else if c.is_ascii_digit() {
+            let mut len: usize = 1;
+            while chars.next_if(|c| c.is_ascii_digit()).is_some() {
+                len += 1;
+            }
+            pieces += len.div_ceil(3);
+        } else {
+            // Punctuation, symbols and non-ASCII characters
+            pieces += 1;
+        }
+    }
+    pieces
+}
+
+pub fn print(estimate: &TokenEstimate) {
+    println!("\nToken estimates:");
+    println!("  Characters / 4: {}", estimate.by_chars());
+    println!("  Words x 4/3: {}", estimate.by_words());
+    println!("  Word pieces: {}", estimate.word_pieces);
+}
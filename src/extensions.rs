@@ -0,0 +1,120 @@
+// Directory mode: when a directory or glob expands to files of more than one
+// extension, the headline stats of each extension follow the totals, since
+// Markdown, plain text and reStructuredText differ in vocabulary and one
+// blended number hides it. A `.gz` suffix is looked past, as reading
+// decompresses it; duplicates left out by `--dedup-docs` take no part.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::redact::shown;
+use crate::report::{fixed, unit};
+use crate::{AnalysisResult, Config, FileStatus};
+
+// `.md`, `.txt`, or `(none)`, lowercased
+pub fn of(path: &str) -> String {
+    let path = Path::new(path);
+    let path = match path.extension() {
+        Some(ext) if ext.eq_ignore_ascii_case("gz") => Path::new(path.file_stem().unwrap_or_default()),
+        _ => path,
+    };
+    path.extension()
+        .map_or("(none)".to_string(), |ext| format!(".{}", ext.to_string_lossy().to_lowercase()))
+}
+
+pub fn mixed(paths: &[String]) -> bool {
+    paths.iter().any(|path| of(path) != of(&paths[0]))
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Group<'a> {
+    pub files: usize,
+    pub tokens: u32,
+    pub freq: HashMap<&'a str, u32>,
+}
+
+impl Group<'_> {
+    // Highest count, alphabetical among ties
+    pub fn most_common(&self) -> Option<(&str, u32)> {
+        self.freq
+            .iter()
+            .map(|(&word, &count)| (word, count))
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+    }
+}
+
+pub fn group(analysis: &AnalysisResult) -> BTreeMap<String, Group<'_>> {
+    let mut groups: BTreeMap<String, Group> = BTreeMap::new();
+    for file in analysis.files.iter().filter(|f| !matches!(f.status, FileStatus::Duplicate { .. })) {
+        let group = groups.entry(of(&file.path)).or_default();
+        group.files += 1;
+        group.tokens += file.tokens;
+        for (word, &count) in file.freq.iter().flatten() {
+            *group.freq.entry(word.as_str()).or_insert(0) += count;
+        }
+    }
+    groups
+}
+
+pub fn print(config: &Config, analysis: &AnalysisResult) {
+    let unit = unit(config);
+    println!("\nBy extension:");
+    for (ext, group) in group(analysis) {
+        let files = if group.files == 1 { "file" } else { "files" };
+        println!("  {} ({} {})", ext, group.files, files);
+        println!("    Total {}s: {}", unit, group.tokens);
+        println!("    Unique {}s: {}", unit, group.freq.len());
+        let ratio = group.freq.len() as f64 / group.tokens.max(1) as f64;
+        println!("    Type-token ratio: {}", fixed(config, ratio, 3));
+        if let Some((word, count)) = group.most_common() {
+            let word = shown(config.redact.as_ref(), word);
+            println!("    Most common {}: \"{}\" with count {}", unit, word, count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileStats;
+
+    fn file(path: &str, words: &[(&str, u32)]) -> FileStats {
+        let freq: HashMap<String, u32> = words.iter().map(|&(w, c)| (w.to_string(), c)).collect();
+        FileStats {
+            path: path.to_string(),
+            status: FileStatus::Counted,
+            tokens: freq.values().sum(),
+            types: freq.len(),
+            sum_length: 0,
+            hapax: 0,
+            grade: None,
+            freq: Some(freq),
+        }
+    }
+
+    #[test]
+    fn extensions_ignore_case_and_gzip() {
+        assert_eq!(of("docs/README.MD"), ".md");
+        assert_eq!(of("notes.txt.gz"), ".txt");
+        assert_eq!(of("LICENSE"), "(none)");
+        assert!(!mixed(&["a.md".to_string(), "b.MD.gz".to_string()]));
+        assert!(mixed(&["a.md".to_string(), "b.txt".to_string()]));
+    }
+
+    #[test]
+    fn files_of_an_extension_add_up() {
+        let analysis = AnalysisResult {
+            files: vec![
+                file("a.md", &[("the", 2), ("code", 3)]),
+                file("b.txt", &[("the", 4)]),
+                file("c.md", &[("the", 3), ("list", 1)]),
+            ],
+            ..AnalysisResult::default()
+        };
+        let groups = group(&analysis);
+        assert_eq!(groups.keys().collect::<Vec<_>>(), [".md", ".txt"]);
+        let md = &groups[".md"];
+        assert_eq!((md.files, md.tokens, md.freq.len()), (2, 9, 3));
+        assert_eq!(md.most_common(), Some(("the", 5)));
+    }
+}
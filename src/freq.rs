@@ -0,0 +1,180 @@
+// Frequency artifacts: the word -> count table as `word<TAB>count` lines,
+// most frequent first. `freq-diff` compares two of them without needing
+// the original texts. `--export-freq` writes the same table as CSV.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::csv::write_field;
+use crate::metadata::RunMetadata;
+use crate::redact::shown;
+use crate::report::fixed;
+use crate::{AnalysisResult, CliError, Config, read_text, stem};
+
+const SHOWN: usize = 20;
+const HAPAX_PER_LINE: usize = 10;
+
+pub fn emit(path: &str, config: &Config, analysis: &AnalysisResult) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    for (word, count) in analysis.ranked() {
+        writeln!(out, "{}\t{}", shown(config.redact.as_ref(), word), count)?;
+    }
+    out.flush()
+}
+
+// `word,count` with a header row, after `#` lines carrying the run
+// metadata; redaction and `--min-count` apply as in the report
+pub fn export_csv(
+    path: &str,
+    config: &Config,
+    analysis: &AnalysisResult,
+    meta: &RunMetadata,
+) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    meta.write_comments(&mut out)?;
+    writeln!(out, "word,count")?;
+    for (word, count) in analysis.reported(config.min_count) {
+        write_field(&mut out, &shown(config.redact.as_ref(), word))?;
+        writeln!(out, ",{}", count)?;
+    }
+    out.flush()
+}
+
+pub fn load(path: &str) -> Result<HashMap<String, u32>, CliError> {
+    let mut freq = HashMap::new();
+    for (number, line) in read_text(path)?.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let parsed = line
+            .rsplit_once('\t')
+            .and_then(|(word, count)| Some((word, count.parse::<u32>().ok()?)));
+        let (word, count) = parsed.ok_or_else(|| CliError::InvalidFreqFile {
+            path: path.to_string(),
+            line: number + 1,
+        })?;
+        *freq.entry(word.to_string()).or_insert(0) += count;
+    }
+    Ok(freq)
+}
+
+// Compares relative frequencies (per 1000 words) so corpora of different
+// sizes line up
+pub fn diff(a_path: &str, b_path: &str) -> Result<(), CliError> {
+    let a = load(a_path)?;
+    let b = load(b_path)?;
+    let total = |f: &HashMap<String, u32>| f.values().map(|&c| c as u64).sum::<u64>().max(1) as f64;
+    let (a_total, b_total) = (total(&a), total(&b));
+
+    let words: HashSet<&String> = a.keys().chain(b.keys()).collect();
+    let mut changes: Vec<(&String, u32, u32, f64, f64)> = words
+        .into_iter()
+        .map(|w| {
+            let ca = a.get(w).copied().unwrap_or(0);
+            let cb = b.get(w).copied().unwrap_or(0);
+            (w, ca, cb, ca as f64 * 1000.0 / a_total, cb as f64 * 1000.0 / b_total)
+        })
+        .collect();
+    changes.sort_by(|x, y| {
+        (y.4 - y.3)
+            .abs()
+            .total_cmp(&(x.4 - x.3).abs())
+            .then(x.0.cmp(y.0))
+    });
+
+    println!("=== Frequency Diff ===");
+    println!("A: {} ({} words, {} unique)", a_path, a_total as u64, a.len());
+    println!("B: {} ({} words, {} unique)", b_path, b_total as u64, b.len());
+    println!("Only in A: {}", a.keys().filter(|w| !b.contains_key(*w)).count());
+    println!("Only in B: {}", b.keys().filter(|w| !a.contains_key(*w)).count());
+    println!("\nLargest changes (per 1000 words):");
+    println!("  {:<20} {:>8} {:>8} {:>9} {:>9} {:>9}", "word", "A", "B", "A/1000", "B/1000", "change");
+    for (word, ca, cb, ra, rb) in changes.into_iter().take(SHOWN) {
+        println!(
+            "  {:<20} {:>8} {:>8} {:>9.2} {:>9.2} {:>+9.2}",
+            word,
+            ca,
+            cb,
+            ra,
+            rb,
+            rb - ra
+        );
+    }
+    Ok(())
+}
+
+// `--rank-of`: count, rank and percentile of the given words. Ranks are
+// shared on ties; the percentile is the share of distinct words counted
+// less often.
+pub fn print_rank_of(config: &Config, analysis: &AnalysisResult) {
+    let mut counts: Vec<u32> = analysis.freq.values().copied().collect();
+    counts.sort_unstable();
+    let types = counts.len().max(1) as f64;
+
+    println!("\nRank of:");
+    for word in &config.rank_of {
+        let key = if config.case_sensitive { word.clone() } else { word.to_lowercase() };
+        let key = if config.stem { stem::stem(&key, config.language) } else { key };
+        let shown_word = shown(config.redact.as_ref(), word);
+        let Some(&count) = analysis.freq.get(&key) else {
+            println!("  {}: not found", shown_word);
+            continue;
+        };
+        let below = counts.partition_point(|&c| c < count);
+        let above = counts.len() - counts.partition_point(|&c| c <= count);
+        println!(
+            "  {}: count {}, rank {} of {}, percentile {}",
+            shown_word,
+            count,
+            above + 1,
+            counts.len(),
+            fixed(config, below as f64 / types * 100.0, 1)
+        );
+    }
+}
+
+// Words counted exactly once, alphabetically
+pub fn hapax(analysis: &AnalysisResult) -> Vec<&String> {
+    let mut words: Vec<&String> = analysis.freq.iter().filter(|&(_, &c)| c == 1).map(|(w, _)| w).collect();
+    words.sort();
+    words
+}
+
+// `--hapax`: the hapax legomena themselves, several to a line
+pub fn print_hapax(config: &Config, analysis: &AnalysisResult) {
+    let words: Vec<String> = hapax(analysis)
+        .into_iter()
+        .map(|w| shown(config.redact.as_ref(), w).into_owned())
+        .collect();
+    println!("\nHapax legomena ({}):", words.len());
+    for row in words.chunks(HAPAX_PER_LINE) {
+        println!("  {}", row.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redact::Redactor;
+
+    #[test]
+    fn emitted_counts_are_redacted() {
+        let config = Config {
+            redact: Some(Redactor::parse("emails,numbers").unwrap()),
+            ..Config::default()
+        };
+        let mut analysis = AnalysisResult::default();
+        for (word, count) in [("bob@example.com", 2), ("555-1234", 1), ("call", 3)] {
+            analysis.freq.insert(word.to_string(), count);
+        }
+        let path = std::env::temp_dir().join(format!("text_analyzer-{}-emit.tsv", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+        emit(&path, &config, &analysis).unwrap();
+        let table = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(!table.contains("bob@example.com") && !table.contains("555"), "{}", table);
+        assert!(table.starts_with("call\t3\n[email:"), "{}", table);
+        assert!(table.contains("[number:"), "{}", table);
+    }
+}
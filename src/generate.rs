@@ -0,0 +1,76 @@
+// `text_analyzer generate FILE`: Markov-chain text from the n-gram model,
+// backing off to shorter histories when a context was never seen.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::lm::NgramModel;
+
+const DEFAULT_SENTENCES: usize = 5;
+const MAX_WORDS: usize = 60;
+
+// splitmix64: tiny, seedable and good enough for picking words
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+pub fn sentences(model: &NgramModel, count: Option<usize>, seed: Option<u64>) -> Vec<String> {
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+    let mut rng = Rng(seed);
+
+    // history -> possible next words, sorted so a seed is reproducible
+    let mut next: HashMap<&[String], Vec<(&str, u32)>> = HashMap::new();
+    for table in &model.counts {
+        for (gram, &c) in table {
+            if let Some((word, history)) = gram.split_last()
+                && word != "<s>"
+            {
+                next.entry(history).or_default().push((word.as_str(), c));
+            }
+        }
+    }
+    for choices in next.values_mut() {
+        choices.sort();
+    }
+
+    (0..count.unwrap_or(DEFAULT_SENTENCES))
+        .map(|_| {
+            let mut words: Vec<String> = vec!["<s>".to_string()];
+            while words.len() <= MAX_WORDS {
+                // Longest known history first, down to plain unigrams
+                let longest = (model.order - 1).min(words.len());
+                let choices = (0..=longest)
+                    .rev()
+                    .find_map(|n| next.get(&words[words.len() - n..]));
+                let Some(choices) = choices else { break };
+                let total: u64 = choices.iter().map(|&(_, c)| c as u64).sum();
+                let mut pick = rng.next() % total;
+                let word = choices
+                    .iter()
+                    .find(|&&(_, c)| {
+                        let hit = pick < c as u64;
+                        pick = pick.saturating_sub(c as u64);
+                        hit
+                    })
+                    .map_or("</s>", |&(w, _)| w);
+                if word == "</s>" {
+                    break;
+                }
+                words.push(word.to_string());
+            }
+            words[1..].join(" ")
+        })
+        .collect()
+}
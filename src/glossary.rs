@@ -0,0 +1,105 @@
+// `--glossary`: abbreviation definitions like "Term Frequency (TF)", found
+// with the Schwartz-Hearst matcher. The long form is the shortest run of
+// preceding words whose characters spell the abbreviation right to left,
+// with its first letter starting a word.
+
+use std::collections::BTreeMap;
+
+use crate::Config;
+use crate::redact::{shown, shown_text};
+
+pub struct Entry {
+    pub short: String,
+    pub long: String,
+    pub definitions: u32,
+    pub uses: u32,
+}
+
+fn is_short_form(candidate: &str) -> bool {
+    let len = candidate.chars().count();
+    (2..=10).contains(&len)
+        && !candidate.contains(char::is_whitespace)
+        && candidate.starts_with(char::is_alphanumeric)
+        && candidate.chars().any(char::is_uppercase)
+}
+
+fn long_form<'a>(short: &str, before: &'a str) -> Option<&'a str> {
+    let short: Vec<char> = short.to_lowercase().chars().collect();
+    let long: Vec<(usize, char)> = before.char_indices().collect();
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let mut s = short.len();
+    let mut l = long.len();
+    while s > 0 {
+        s -= 1;
+        let c = short[s];
+        if !c.is_alphanumeric() {
+            continue;
+        }
+        loop {
+            if l == 0 {
+                return None;
+            }
+            l -= 1;
+            let at_word_start = l == 0 || !long[l - 1].1.is_alphanumeric();
+            if lower(long[l].1) == c && (s > 0 || at_word_start) {
+                break;
+            }
+        }
+    }
+    let start = before[..long[l].0].rfind(char::is_whitespace).map_or(0, |i| i + 1);
+    Some(before[start..].trim())
+}
+
+pub fn extract(text: &str) -> Vec<Entry> {
+    let mut found: BTreeMap<(String, String), u32> = BTreeMap::new();
+    for (open, _) in text.match_indices('(') {
+        let Some(close) = text[open..].find(')').map(|i| open + i) else {
+            continue;
+        };
+        let short = text[open + 1..close].trim();
+        if !is_short_form(short) {
+            continue;
+        }
+        // At most min(|A| + 5, |A| * 2) words before the parenthesis
+        let len = short.chars().count();
+        let limit = (len + 5).min(len * 2);
+        let sentence = &text[..open];
+        let sentence = &sentence[sentence.rfind(['.', '!', '?', '(', ')', ';']).map_or(0, |i| i + 1)..];
+        let words: Vec<&str> = sentence.split_whitespace().collect();
+        let window = words[words.len().saturating_sub(limit)..].join(" ");
+        if let Some(long) = long_form(short, &window)
+            && long.split_whitespace().count() > 1
+        {
+            *found.entry((short.to_string(), long.to_lowercase())).or_insert(0) += 1;
+        }
+    }
+
+    found
+        .into_iter()
+        .map(|((short, long), definitions)| {
+            // Uses outside the definitions themselves
+            let uses = text
+                .split_whitespace()
+                .filter(|w| w.trim_matches(|c: char| !c.is_alphanumeric()) == short)
+                .count() as u32;
+            Entry { uses: uses.saturating_sub(definitions), short, long, definitions }
+        })
+        .collect()
+}
+
+pub fn print(config: &Config, entries: &[Entry]) {
+    let redactor = config.redact.as_ref();
+    println!("\nGlossary:");
+    if entries.is_empty() {
+        println!("  No abbreviation definitions found");
+    }
+    for e in entries {
+        println!(
+            "  {:<10} {} (defined {}, used {} more)",
+            shown(redactor, &e.short),
+            shown_text(redactor, &e.long),
+            e.definitions,
+            e.uses
+        );
+    }
+}
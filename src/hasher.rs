@@ -0,0 +1,100 @@
+// `--hasher fx|siphash`: the hash function behind the frequency and position
+// maps. SipHash (the std default, randomly keyed) resists hash flooding;
+// Fx is several times faster on short keys but only safe on trusted input.
+// The choice is made at run time, so the maps carry an enum hasher rather
+// than a type parameter; library users who want a fixed hasher can count
+// with `count_words` instead.
+
+use std::collections::HashMap;
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::hash::{BuildHasher, Hasher};
+
+use rustc_hash::FxHasher;
+
+use crate::{Config, tokens};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HasherKind {
+    Fx,
+    #[default]
+    SipHash,
+}
+
+impl HasherKind {
+    pub fn parse(name: &str) -> Option<HasherKind> {
+        match name {
+            "fx" => Some(HasherKind::Fx),
+            "siphash" | "sip" => Some(HasherKind::SipHash),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum WordState {
+    Fx,
+    SipHash(RandomState),
+}
+
+impl WordState {
+    pub fn new(kind: HasherKind) -> WordState {
+        match kind {
+            HasherKind::Fx => WordState::Fx,
+            HasherKind::SipHash => WordState::SipHash(RandomState::new()),
+        }
+    }
+}
+
+impl Default for WordState {
+    fn default() -> Self {
+        WordState::new(HasherKind::default())
+    }
+}
+
+pub enum WordHasher {
+    Fx(FxHasher),
+    SipHash(DefaultHasher),
+}
+
+impl BuildHasher for WordState {
+    type Hasher = WordHasher;
+
+    fn build_hasher(&self) -> WordHasher {
+        match self {
+            WordState::Fx => WordHasher::Fx(FxHasher::default()),
+            WordState::SipHash(state) => WordHasher::SipHash(state.build_hasher()),
+        }
+    }
+}
+
+impl Hasher for WordHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        match self {
+            WordHasher::Fx(h) => h.write(bytes),
+            WordHasher::SipHash(h) => h.write(bytes),
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        match self {
+            WordHasher::Fx(h) => h.write_u8(value),
+            WordHasher::SipHash(h) => h.write_u8(value),
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        match self {
+            WordHasher::Fx(h) => h.finish(),
+            WordHasher::SipHash(h) => h.finish(),
+        }
+    }
+}
+
+// Word counts alone, in a map with any hasher
+pub fn count_words<S: BuildHasher + Default>(text: &str, config: &Config) -> HashMap<String, u32, S> {
+    let mut freq = HashMap::default();
+    for (_, word) in tokens(text, config) {
+        *freq.entry(word).or_insert(0) += 1;
+    }
+    freq
+}
@@ -0,0 +1,53 @@
+// `--export-idf FILE`: inverse document frequency over the corpus, with
+// each input file as one document. Writes `word,df,idf` rows, most
+// widespread words first, with idf = ln(documents / df); the counts use the
+// same normalization and filters as the report.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use rayon::prelude::*;
+
+use crate::csv::write_field;
+use crate::metadata::RunMetadata;
+use crate::redact::shown;
+use crate::{Config, Prepared, tokens};
+
+pub struct DocumentFrequencies {
+    // Documents each word appears in
+    pub df: HashMap<String, u32>,
+    pub documents: usize,
+}
+
+// From the inputs as read for the counts, so standard input works too
+pub fn document_frequencies(prepared: &Prepared, config: &Config) -> DocumentFrequencies {
+    let vocabularies: Vec<HashSet<String>> = prepared
+        .documents
+        .par_iter()
+        .map(|(_, range)| tokens(&prepared.text[range.clone()], config).map(|(_, word)| word).collect())
+        .collect();
+    let mut df = HashMap::new();
+    for vocabulary in vocabularies {
+        for word in vocabulary {
+            *df.entry(word).or_insert(0) += 1;
+        }
+    }
+    DocumentFrequencies { df, documents: prepared.documents.len() }
+}
+
+pub fn export(path: &str, config: &Config, frequencies: &DocumentFrequencies, meta: &RunMetadata) -> io::Result<()> {
+    let DocumentFrequencies { df, documents } = frequencies;
+    let documents = *documents;
+    let mut rows: Vec<(&String, u32)> = df.iter().map(|(w, &d)| (w, d)).collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    let mut out = BufWriter::new(File::create(path)?);
+    meta.write_comments(&mut out)?;
+    writeln!(out, "# documents: {}", documents)?;
+    writeln!(out, "word,df,idf")?;
+    for (word, count) in rows {
+        write_field(&mut out, &shown(config.redact.as_ref(), word))?;
+        writeln!(out, ",{},{:.6}", count, (documents as f64 / count as f64).ln())?;
+    }
+    out.flush()
+}
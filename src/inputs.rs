@@ -0,0 +1,153 @@
+// Input expansion: a directory (with `--recursive`) or a glob such as
+// `docs/**/*.md` stands for every text file under it, in sorted order.
+// Expanded files that look binary (a NUL byte near the start) are skipped
+// with a warning; paths named directly are always read. UTF-16 text has NUL
+// bytes too, so a byte-order mark or NULs in every other byte keep a file.
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::encoding::SNIFF_BYTES;
+use crate::{CliError, STDIN_PATH};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+pub fn expand(args: &[String], recursive: bool) -> Result<Vec<String>, CliError> {
+    let mut files = Vec::new();
+    for arg in args {
+        let path = Path::new(arg);
+        if arg != STDIN_PATH && path.is_dir() {
+            if !recursive {
+                return Err(CliError::FileReadError {
+                    path: arg.clone(),
+                    reason: "is a directory (add --recursive to read it)".to_string(),
+                });
+            }
+            let mut found = Vec::new();
+            walk(path, &mut found).map_err(|e| CliError::FileReadError {
+                path: arg.clone(),
+                reason: e.to_string(),
+            })?;
+            found.sort();
+            files.extend(text_files(found));
+        } else if arg.contains(['*', '?', '[']) && !path.exists() {
+            let matches = glob::glob(arg).map_err(|e| CliError::FileReadError {
+                path: arg.clone(),
+                reason: e.to_string(),
+            })?;
+            let found: Vec<PathBuf> = matches.filter_map(Result::ok).filter(|p| p.is_file()).collect();
+            if found.is_empty() {
+                return Err(CliError::FileNotFound(arg.clone()));
+            }
+            files.extend(text_files(found));
+        } else {
+            files.push(arg.clone());
+        }
+    }
+    Ok(files)
+}
+
+// Symlinked directories are not followed, so link cycles cannot loop
+fn walk(dir: &Path, found: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let kind = fs::symlink_metadata(&path)?.file_type();
+        if kind.is_dir() {
+            walk(&path, found)?;
+        } else if path.is_file() {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn text_files(paths: Vec<PathBuf>) -> impl Iterator<Item = String> {
+    paths.into_iter().filter_map(|path| {
+        let path = path.to_string_lossy().into_owned();
+        if is_binary(&path) {
+            eprintln!("Warning: Skipping binary file '{}'", path);
+            None
+        } else {
+            Some(path)
+        }
+    })
+}
+
+// Gzip counts as text, since reading decompresses it
+fn is_binary(path: &str) -> bool {
+    let mut head = Vec::with_capacity(SNIFF_BYTES);
+    let read = File::open(path).and_then(|f| f.take(SNIFF_BYTES as u64).read_to_end(&mut head));
+    read.is_ok()
+        && !head.starts_with(&GZIP_MAGIC)
+        && head.contains(&0)
+        && !is_utf16(&head)
+}
+
+// Without a byte-order mark, UTF-16 of mostly Latin text has a NUL in at
+// least half of its code units, and all of them on the same side
+fn is_utf16(head: &[u8]) -> bool {
+    if head.starts_with(&[0xff, 0xfe]) || head.starts_with(&[0xfe, 0xff]) {
+        return true;
+    }
+    let pairs = head.len() / 2;
+    let nuls = |offset: usize| head.iter().skip(offset).step_by(2).filter(|&&b| b == 0).count();
+    let (even, odd) = (nuls(0), nuls(1));
+    pairs > 0 && ((even == 0 && odd * 2 >= pairs) || (odd == 0 && even * 2 >= pairs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    // Named files go to std::fs unchanged, whatever their separators
+    #[test]
+    fn drive_letter_and_backslash_paths_pass_through() {
+        let paths = strings(&[r"C:\docs\notes.txt", "D:/notes/b.md", r"\\server\share\c.txt", r"rel\d.txt"]);
+        assert_eq!(expand(&paths, false).unwrap(), paths);
+    }
+
+    #[test]
+    fn a_directory_expands_under_its_own_prefix() {
+        let dir = std::env::temp_dir().join(format!("text_analyzer-{}-walk", std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("a.txt"), "a\n").unwrap();
+        fs::write(dir.join("sub").join("b.md"), "b\n").unwrap();
+        let root = dir.to_string_lossy().into_owned();
+        let found = expand(std::slice::from_ref(&root), true).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let expected = [dir.join("a.txt"), dir.join("sub").join("b.md")];
+        assert_eq!(found, expected.map(|p| p.to_string_lossy().into_owned()));
+    }
+
+    // A backslash glob, as typed in cmd.exe, matches on Windows
+    #[cfg(windows)]
+    #[test]
+    fn backslash_globs_match_on_windows() {
+        let dir = std::env::temp_dir().join(format!("text_analyzer-{}-glob", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.txt"), "a\n").unwrap();
+        let pattern = format!(r"{}\*.txt", dir.display());
+        let found = expand(&[pattern], false).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(found, [dir.join("a.txt").to_string_lossy().into_owned()]);
+    }
+
+    #[test]
+    fn a_stray_nul_is_not_utf16() {
+        assert!(!is_utf16(b"bin\0ary"));
+        assert!(!is_utf16(b"\0a\0b c d e f"));
+    }
+
+    #[test]
+    fn utf16_is_told_by_its_mark_or_its_nuls() {
+        assert!(is_utf16(b"\xff\xfeb\0i\0n\0"));
+        assert!(is_utf16(b"\xfe\xff\0b\0i\0n"));
+        assert!(is_utf16(b"b\0i\0n\0a\0r\0y\0"));
+        assert!(is_utf16(b"\0b\0i\0n\0a\0r\0y"));
+    }
+}
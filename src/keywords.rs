@@ -0,0 +1,224 @@
+// `--keywords`: single-document keyword extraction. RAKE splits the text
+// into candidate phrases at stopwords and punctuation, scores each word by
+// degree / frequency and each phrase by the sum of its word scores.
+// TextRank runs PageRank over a co-occurrence graph of content words and
+// joins adjacent top-ranked words back into phrases; the same iteration over
+// a sentence-similarity graph picks the sentences for `--summary`.
+
+use std::collections::HashMap;
+
+use crate::Config;
+use crate::redact::shown_text;
+use crate::report::fixed;
+use crate::sentence::{self, SentenceRules};
+use crate::stopwords;
+
+const KEYWORDS_SHOWN: usize = 10;
+const DAMPING: f64 = 0.85;
+const MAX_ITERATIONS: usize = 100;
+const CONVERGED: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Method {
+    Rake,
+    TextRank,
+}
+
+impl Method {
+    pub fn parse(name: &str) -> Option<Method> {
+        match name {
+            "rake" => Some(Method::Rake),
+            "textrank" => Some(Method::TextRank),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Method::Rake => "RAKE",
+            Method::TextRank => "TextRank",
+        }
+    }
+}
+
+pub struct Keywords {
+    pub method: Method,
+    pub ranked: Vec<(String, f64)>,
+}
+
+pub fn extract(text: &str, method: Method) -> Keywords {
+    let ranked = match method {
+        Method::Rake => rake(text),
+        Method::TextRank => textrank(text),
+    };
+    Keywords { method, ranked }
+}
+
+// Lowercased words with edge punctuation removed; `true` when punctuation
+// after the word ends the phrase
+pub fn words(text: &str) -> impl Iterator<Item = (String, bool)> + '_ {
+    text.split_whitespace().map(|raw| {
+        let word = raw.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        let breaks = raw.ends_with(['.', ',', ';', ':', '!', '?', ')', '"'])
+            || raw.starts_with(['(', '"']);
+        (word, breaks)
+    })
+}
+
+// Candidate phrases: runs of content words between stopwords and punctuation
+fn candidates(text: &str) -> Vec<Vec<String>> {
+    let mut phrases = Vec::new();
+    let mut current = Vec::new();
+    for (word, breaks) in words(text) {
+        let content = word.chars().any(char::is_alphabetic) && !stopwords::is_stopword(&word);
+        if content {
+            current.push(word);
+        }
+        if (!content || breaks) && !current.is_empty() {
+            phrases.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        phrases.push(current);
+    }
+    phrases
+}
+
+fn rake(text: &str) -> Vec<(String, f64)> {
+    let phrases = candidates(text);
+    // Degree counts the word itself plus its co-members in each phrase
+    let mut freq: HashMap<&str, f64> = HashMap::new();
+    let mut degree: HashMap<&str, f64> = HashMap::new();
+    for phrase in &phrases {
+        for word in phrase {
+            *freq.entry(word).or_insert(0.0) += 1.0;
+            *degree.entry(word).or_insert(0.0) += phrase.len() as f64;
+        }
+    }
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    for phrase in &phrases {
+        let score = phrase.iter().map(|w| degree[w.as_str()] / freq[w.as_str()]).sum();
+        scores.insert(phrase.join(" "), score);
+    }
+    let mut ranked: Vec<(String, f64)> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked.truncate(KEYWORDS_SHOWN);
+    ranked
+}
+
+// Weighted PageRank; `edges[i]` lists the neighbours of vertex i
+fn pagerank(edges: &[Vec<(usize, f64)>]) -> Vec<f64> {
+    let n = edges.len();
+    let out: Vec<f64> = edges.iter().map(|e| e.iter().map(|&(_, w)| w).sum()).collect();
+    let mut rank = vec![1.0; n];
+    for _ in 0..MAX_ITERATIONS {
+        let next: Vec<f64> = (0..n)
+            .map(|i| {
+                let inflow: f64 = edges[i].iter().map(|&(j, w)| w / out[j] * rank[j]).sum();
+                (1.0 - DAMPING) + DAMPING * inflow
+            })
+            .collect();
+        let delta = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+        rank = next;
+        if delta < CONVERGED {
+            break;
+        }
+    }
+    rank
+}
+
+fn textrank(text: &str) -> Vec<(String, f64)> {
+    let phrases = candidates(text);
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    let sequence: Vec<usize> = phrases
+        .iter()
+        .flatten()
+        .map(|w| {
+            let next = index.len();
+            *index.entry(w.as_str()).or_insert(next)
+        })
+        .collect();
+
+    // Window of two over the content words, undirected and unweighted
+    let mut weights: HashMap<(usize, usize), f64> = HashMap::new();
+    for pair in sequence.windows(2) {
+        if pair[0] != pair[1] {
+            weights.insert((pair[0], pair[1]), 1.0);
+            weights.insert((pair[1], pair[0]), 1.0);
+        }
+    }
+    let mut edges = vec![Vec::new(); index.len()];
+    for (&(a, b), &w) in &weights {
+        edges[a].push((b, w));
+    }
+    let rank = pagerank(&edges);
+
+    // The top third of the vertices are keywords; adjacent ones form phrases
+    let mut order: Vec<usize> = (0..rank.len()).collect();
+    order.sort_by(|&a, &b| rank[b].total_cmp(&rank[a]));
+    let keep = (rank.len() / 3).max(KEYWORDS_SHOWN.min(rank.len()));
+    let mut selected = vec![false; rank.len()];
+    for &v in &order[..keep] {
+        selected[v] = true;
+    }
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    for phrase in &phrases {
+        for run in phrase.split(|w| !selected[index[w.as_str()]]) {
+            if !run.is_empty() {
+                let score = run.iter().map(|w| rank[index[w.as_str()]]).sum();
+                scores.insert(run.join(" "), score);
+            }
+        }
+    }
+    let mut ranked: Vec<(String, f64)> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked.truncate(KEYWORDS_SHOWN);
+    ranked
+}
+
+// The `count` highest-ranked sentences, in document order. Similarity is
+// the number of shared content words normalized by the sentence lengths.
+pub fn summarize<'a>(text: &'a str, rules: &SentenceRules, count: usize) -> Vec<&'a str> {
+    let sentences = sentence::split(text, rules);
+    let bags: Vec<Vec<String>> = sentences
+        .iter()
+        .map(|s| {
+            let mut bag: Vec<String> = candidates(s).into_iter().flatten().collect();
+            bag.sort();
+            bag.dedup();
+            bag
+        })
+        .collect();
+    let mut edges = vec![Vec::new(); sentences.len()];
+    for a in 0..bags.len() {
+        for b in a + 1..bags.len() {
+            let shared = bags[a].iter().filter(|w| bags[b].binary_search(w).is_ok()).count();
+            let norm = (bags[a].len() as f64).ln() + (bags[b].len() as f64).ln();
+            if shared > 0 && norm > 0.0 {
+                edges[a].push((b, shared as f64 / norm));
+                edges[b].push((a, shared as f64 / norm));
+            }
+        }
+    }
+    let rank = pagerank(&edges);
+    let mut order: Vec<usize> = (0..sentences.len()).collect();
+    order.sort_by(|&a, &b| rank[b].total_cmp(&rank[a]).then(a.cmp(&b)));
+    order.truncate(count);
+    order.sort();
+    order.into_iter().map(|i| sentences[i]).collect()
+}
+
+pub fn print_summary(config: &Config, sentences: &[String]) {
+    println!("\nSummary:");
+    for s in sentences {
+        let flat = s.split_whitespace().collect::<Vec<_>>().join(" ");
+        println!("  {}", shown_text(config.redact.as_ref(), &flat));
+    }
+}
+
+pub fn print(config: &Config, keywords: &Keywords) {
+    println!("\nKeywords ({}):", keywords.method.name());
+    for (phrase, score) in &keywords.ranked {
+        println!("  {:<40} {}", shown_text(config.redact.as_ref(), phrase), fixed(config, *score, 2));
+    }
+}
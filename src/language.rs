@@ -0,0 +1,205 @@
+// Languages with their own syllable rules, and a function-word based
+// detector for when `--language` is not given, also run per paragraph to
+// report mixed-language texts.
+
+use crate::Config;
+use crate::report::fixed;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    English,
+    Spanish,
+    German,
+    French,
+}
+
+const LANGUAGES: [Language; 4] = [
+    Language::English,
+    Language::Spanish,
+    Language::German,
+    Language::French,
+];
+
+impl Language {
+    pub fn parse(name: &str) -> Option<Language> {
+        match name {
+            "en" | "english" => Some(Language::English),
+            "es" | "spanish" => Some(Language::Spanish),
+            "de" | "german" => Some(Language::German),
+            "fr" | "french" => Some(Language::French),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "english",
+            Language::Spanish => "spanish",
+            Language::German => "german",
+            Language::French => "french",
+        }
+    }
+
+    // Very frequent words that are rare in the other supported languages
+    fn markers(self) -> &'static [&'static str] {
+        match self {
+            Language::English => &["the", "and", "of", "to", "is", "that", "with", "was", "for", "it"],
+            Language::Spanish => &["el", "la", "que", "y", "los", "del", "las", "por", "con", "una"],
+            Language::German => &["der", "die", "und", "das", "ist", "nicht", "mit", "ein", "zu", "ich"],
+            Language::French => &["le", "les", "et", "des", "est", "une", "que", "pas", "pour", "dans"],
+        }
+    }
+
+    pub fn syllables(self, word: &str) -> usize {
+        let letters: Vec<char> = word
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .flat_map(char::to_lowercase)
+            .collect();
+        let count = match self {
+            Language::English => english(&letters),
+            Language::Spanish => spanish(&letters),
+            Language::German => vowel_groups(&letters, "aeiouyäöü"),
+            Language::French => french(&letters),
+        };
+        count.max(1)
+    }
+}
+
+// Language whose marker words cover the most tokens; English on a tie
+pub fn detect(text: &str) -> Language {
+    identify(text).unwrap_or(Language::English)
+}
+
+// As `detect`, but None when no marker word appears at all
+pub fn identify(text: &str) -> Option<Language> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase())
+        .collect();
+    let mut best = (None, 0);
+    for language in LANGUAGES {
+        let hits = words
+            .iter()
+            .filter(|w| language.markers().contains(&w.as_str()))
+            .count();
+        if hits > best.1 {
+            best = (Some(language), hits);
+        }
+    }
+    best.0
+}
+
+// Share of the words above which a second language makes a text mixed
+const MIXED_SHARE: f64 = 0.1;
+
+// `--language-mix`: words per language, identified paragraph by paragraph
+// (blocks between blank lines)
+pub struct Mix {
+    pub paragraphs: usize,
+    // Most words first; paragraphs without marker words count as None
+    pub words: Vec<(Option<Language>, usize)>,
+}
+
+impl Mix {
+    fn total(&self) -> usize {
+        self.words.iter().map(|w| w.1).sum()
+    }
+
+    // More than one language holds a substantial share of the words
+    pub fn is_mixed(&self) -> bool {
+        let total = self.total().max(1) as f64;
+        let substantial = self
+            .words
+            .iter()
+            .filter(|(language, words)| language.is_some() && *words as f64 / total >= MIXED_SHARE);
+        substantial.count() > 1
+    }
+}
+
+pub fn mix(text: &str) -> Mix {
+    let mut words: Vec<(Option<Language>, usize)> = Vec::new();
+    let mut paragraphs = 0;
+    let mut add = |paragraph: &str| {
+        let count = paragraph.split_whitespace().count();
+        if count == 0 {
+            return;
+        }
+        paragraphs += 1;
+        let language = identify(paragraph);
+        match words.iter_mut().find(|w| w.0 == language) {
+            Some(entry) => entry.1 += count,
+            None => words.push((language, count)),
+        }
+    };
+    let mut paragraph = String::new();
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            add(&paragraph);
+            paragraph.clear();
+        } else {
+            paragraph.push_str(line);
+            paragraph.push('\n');
+        }
+    }
+    add(&paragraph);
+    words.sort_by_key(|w| std::cmp::Reverse(w.1));
+    Mix { paragraphs, words }
+}
+
+pub fn print_mix(config: &Config, mix: &Mix) {
+    let total = mix.total().max(1) as f64;
+    println!("\nLanguage mix:");
+    println!("  Paragraphs: {}", mix.paragraphs);
+    for (language, words) in &mix.words {
+        let name = language.map_or("unidentified", |l| l.name());
+        let share = *words as f64 / total * 100.0;
+        println!("  {:<13} {:>5}% ({} words)", format!("{}:", name), fixed(config, share, 1), words);
+    }
+    println!("  Mixed: {}", if mix.is_mixed() { "yes" } else { "no" });
+}
+
+fn vowel_groups(letters: &[char], vowels: &str) -> usize {
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for &c in letters {
+        let vowel = vowels.contains(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+    count
+}
+
+// Vowel groups minus a silent final "e" (but not "-le" as in "table")
+fn english(letters: &[char]) -> usize {
+    let is_vowel = |c: char| "aeiouy".contains(c);
+    let mut count = vowel_groups(letters, "aeiouy");
+    let n = letters.len();
+    if n > 2 && letters[n - 1] == 'e' && letters[n - 2] != 'l' && !is_vowel(letters[n - 2]) {
+        count = count.saturating_sub(1);
+    }
+    count
+}
+
+// Vowel groups, where two strong vowels (a, e, o or an accented i/u) in a
+// row are a hiatus and split into separate syllables
+fn spanish(letters: &[char]) -> usize {
+    let strong = |c: char| "aeoáéóíú".contains(c);
+    let hiatuses = letters
+        .windows(2)
+        .filter(|pair| strong(pair[0]) && strong(pair[1]))
+        .count();
+    vowel_groups(letters, "aeiouáéíóúü") + hiatuses
+}
+
+// Vowel groups minus the mute final "e"/"es"
+fn french(letters: &[char]) -> usize {
+    let count = vowel_groups(letters, "aeiouyàâéèêëîïôûùü");
+    let mute = match letters {
+        [.., c, 'e'] | [.., c, 'e', 's'] => !"aeiouyàâéèêëîïôûùü".contains(*c),
+        _ => false,
+    };
+    if mute && count > 1 { count - 1 } else { count }
+}
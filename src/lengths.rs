@@ -0,0 +1,35 @@
+// `--length-dist`: how many counted words have each length in grapheme
+// clusters, from 1 up to the longest. Works from the frequency map, so it
+// runs from a session file too. `--histogram` adds a bar per length.
+
+use crate::report::fixed;
+use crate::{AnalysisResult, Config, chart, word_length};
+
+// Tokens of each length, indexed by length
+pub fn distribution(analysis: &AnalysisResult) -> Vec<u32> {
+    let mut counts = Vec::new();
+    for (word, &count) in &analysis.freq {
+        let length = word_length(word);
+        if counts.len() <= length {
+            counts.resize(length + 1, 0);
+        }
+        counts[length] += count;
+    }
+    counts
+}
+
+pub fn print(config: &Config, analysis: &AnalysisResult) {
+    let counts = distribution(analysis);
+    let total = analysis.total_words().max(1) as f64;
+    let max = counts.iter().copied().max().unwrap_or(0);
+
+    println!("\nWord lengths:");
+    for (length, &count) in counts.iter().enumerate().skip(1) {
+        let share = fixed(config, count as f64 / total * 100.0, 1);
+        let row = format!("  {:>3}: {:>7} {:>6}%", length, count, share);
+        match config.histogram && count > 0 {
+            true => println!("{} {}", row, chart::bar(count, max)),
+            false => println!("{}", row),
+        }
+    }
+}
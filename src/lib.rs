@@ -0,0 +1,1523 @@
+// Library half of the analyzer: `Config`, `analyze(text, &Config)` and the
+// report/export modules, so other programs can reuse the analysis. The
+// binary in main.rs only wires the command line to them.
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, IsTerminal, Read};
+use std::ops::Range;
+
+use flate2::read::MultiGzDecoder;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub mod bands;
+pub mod brevity;
+pub mod chars;
+pub mod chart;
+pub mod checkpoint;
+pub mod contractions;
+pub mod cooccurrence;
+pub mod csv;
+pub mod dates;
+pub mod dedup;
+pub mod demo;
+pub mod diversity;
+pub mod encoding;
+pub mod estimate;
+pub mod extensions;
+pub mod freq;
+pub mod generate;
+pub mod glossary;
+pub mod hasher;
+pub mod idf;
+pub mod inputs;
+pub mod json;
+pub mod keywords;
+pub mod language;
+pub mod lengths;
+pub mod lm;
+pub mod memory;
+pub mod metadata;
+pub mod number_words;
+pub mod numbers;
+pub mod numerals;
+pub mod outliers;
+pub mod per_line;
+pub mod phrases;
+pub mod pipeline;
+pub mod preprocess;
+pub mod pronouns;
+pub mod readability;
+pub mod reader;
+pub mod recase;
+pub mod redact;
+pub mod report;
+pub mod sentence;
+pub mod sentence_types;
+pub mod sentiment;
+pub mod session;
+pub mod stem;
+pub mod stopwords;
+pub mod structure;
+pub mod table;
+pub mod temporal;
+pub mod token_stream;
+pub mod tokenizer;
+pub mod trace;
+pub mod transcript;
+pub mod wc;
+pub mod weights;
+pub mod whitespace;
+
+use hasher::{HasherKind, WordState};
+use readability::Audience;
+use report::{Format, SortOrder, Stat};
+pub use report::{print_json, print_report};
+use sentence::SentenceRules;
+use tokenizer::Tokenizer;
+
+pub const STDIN_PATH: &str = "-";
+
+// Enum for error states (#14: Enum, #16: Pattern Matching)
+#[derive(Debug)]
+pub enum CliError {
+    MissingFilePath,
+    InvalidMinLength { value: String, reason: String },
+    InvalidStartsWith { value: String, reason: String },
+    FileNotFound(String),
+    FileReadPermission(String),
+    FileReadError { path: String, reason: String },
+    EmptyFile,
+    SessionError { path: String, reason: String },
+    InvalidStat { value: String, reason: String },
+    InvalidAudience(String),
+    InvalidLanguage(String),
+    AudienceNotMet { audience: Audience, grade: f64 },
+    InvalidNumber { flag: String, value: String },
+    InvalidRedact(String),
+    InvalidNumberStyle(String),
+    InvalidInputFormat(String),
+    InvalidKeywords(String),
+    InvalidFormat(String),
+    InvalidTokenPattern { value: String, reason: String },
+    InvalidHasher(String),
+    InvalidSort(String),
+    InvalidMatch { flag: String, value: String, reason: String },
+    InvalidTokenizer(String),
+    InvalidWeights { path: String, line: usize },
+    ConflictingOptions { first: String, second: String, reason: String },
+    InvalidThreshold(String),
+    MissingValue(String),
+    InvalidFreqFile { path: String, line: usize },
+    ExportError { path: String, reason: String },
+}
+
+// Builder Pattern for configuration (#1)
+#[derive(Default)]
+pub struct Config {
+    // Shown in the report header: the input files after directory and glob
+    // expansion, comma-separated
+    pub file_path: String,
+    pub files: Vec<String>,
+    pub recursive: bool,
+    pub memory_report: bool,
+    pub hasher: HasherKind,
+    pub sort: SortOrder,
+    // Counts runs of N tokens instead of single words
+    pub ngrams: Option<usize>,
+    // Words left out of the counts entirely
+    pub stopwords: Option<stopwords::StopList>,
+    pub reverse: bool,
+    // Per-file counting with a state file to resume from
+    pub resume: Option<String>,
+    pub checkpoint: Option<usize>,
+    // Concurrent file reads with several inputs
+    pub jobs: Option<usize>,
+    pub min_length: usize,
+    // Words counted fewer times are left out of word lists, not totals
+    pub min_count: usize,
+    pub starts_with: Option<char>,
+    pub save_session: Option<String>,
+    pub load_session: Option<String>,
+    pub per_line: bool,
+    pub detailed: bool,
+    pub content_words_only: bool,
+    pub stats: Vec<Stat>,
+    pub whitespace: bool,
+    pub audience: Option<Audience>,
+    pub phrases: Vec<String>,
+    pub char_pairs: bool,
+    pub alphabet: bool,
+    pub readability: bool,
+    pub sentence_stats: bool,
+    pub language_mix: bool,
+    // Decimals for floating-point stats; each has its own default
+    pub precision: Option<usize>,
+    pub numbers: bool,
+    pub length_dist: bool,
+    // Bars next to the length distribution
+    pub histogram: bool,
+    pub dates: bool,
+    pub sentiment_arc: bool,
+    pub export_cooccurrence: Option<String>,
+    pub cooccur_window: Option<usize>,
+    // Printed as a ranked list; also sizes the co-occurrence matrix
+    pub top: Option<usize>,
+    // Bars next to the ranked list
+    pub chart: bool,
+    // Words in the "most common" stat, at least 1
+    pub leaders: Option<usize>,
+    pub sentence_rules: SentenceRules,
+    pub sentence_report: Option<String>,
+    pub bands: Option<bands::Reference>,
+    pub weights: Option<weights::Weights>,
+    pub export_lm: Option<String>,
+    pub export_tokens: Option<String>,
+    pub trace_tokens: Option<usize>,
+    pub export_idf: Option<String>,
+    pub suggest_stopwords: Option<usize>,
+    pub export_stopwords: Option<String>,
+    pub lm_order: Option<usize>,
+    pub sentences: Option<usize>,
+    pub seed: Option<u64>,
+    pub estimate: bool,
+    pub wc: bool,
+    pub redact: Option<redact::Redactor>,
+    pub emit_freq: Option<String>,
+    pub language: Option<language::Language>,
+    pub rejoin_hyphenation: bool,
+    pub ocr_cleanup: bool,
+    pub recase: bool,
+    pub keep_unicode_punct: bool,
+    pub expand_contractions: bool,
+    pub normalize_numbers: Option<number_words::NumberStyle>,
+    pub keep_numerals: bool,
+    pub input_format: Option<transcript::InputFormat>,
+    pub keywords: Option<keywords::Method>,
+    pub summary: Option<usize>,
+    pub top_phrases: Option<usize>,
+    pub max_phrase_len: Option<usize>,
+    pub glossary: bool,
+    pub sentence_types: bool,
+    pub format: Format,
+    pub pronouns: bool,
+    pub export_freq: Option<String>,
+    pub export_brevity: Option<String>,
+    pub rank_of: Vec<String>,
+    // Lists the words counted once
+    pub hapax: bool,
+    // Words dropped from counting, `--exclude` and `--exclude-file`
+    pub exclude: HashSet<String>,
+    // Set by --temporal, or with a custom lexicon by --temporal-lexicon
+    pub temporal: Option<temporal::Lexicon>,
+    pub tokenizer: Tokenizer,
+    // Replaces the tokenizer when given
+    pub token_pattern: Option<regex::Regex>,
+    // Only words matching `match_pattern` are counted, and none matching
+    // `exclude_match`
+    pub match_pattern: Option<regex::Regex>,
+    // Keeps the case of words; stopwords still match whatever the case
+    pub case_sensitive: bool,
+    // `--ends-with` and `--contains`, next to `starts_with`
+    pub ends_with: Option<String>,
+    pub contains: Option<String>,
+    pub stem: bool,
+    // Counts characters instead of words
+    pub chars: bool,
+    // Leaves punctuation on the ends of words, `--keep-punct`
+    pub keep_punct: bool,
+    pub exclude_match: Option<regex::Regex>,
+    // Hash of every argument but the input paths, for run metadata
+    pub options_hash: u64,
+    // Corpus mode: the N inputs that diverge most from the average
+    pub outliers: Option<usize>,
+    // Corpus mode: inputs at least this similar to an earlier one are left out
+    pub dedup_docs: Option<f64>,
+    // A directory or glob expanded to files of several extensions
+    pub by_extension: bool,
+}
+
+impl Config {
+    pub fn new(args: Vec<String>) -> Result<Self, CliError> {
+        let mut config = Config::default();
+
+        let mut i = 1;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--min-length" => {
+                    i += 1;
+                    config.min_length = args
+                        .get(i)
+                        .ok_or_else(|| CliError::InvalidMinLength {
+                            value: "".to_string(),
+                            reason: "Missing value".to_string(),
+                        })?
+                        .parse()
+                        .map_err(|_| CliError::InvalidMinLength {
+                            value: args[i].clone(),
+                            reason: "Not a number".to_string(),
+                        })?;
+                }
+                "--starts-with" => {
+                    i += 1;
+                    let c = args
+                        .get(i)
+                        .ok_or_else(|| CliError::InvalidStartsWith {
+                            value: "".to_string(),
+                            reason: "Missing value".to_string(),
+                        })?
+                        .chars()
+                        .next()
+                        .ok_or_else(|| CliError::InvalidStartsWith {
+                            value: args[i].clone(),
+                            reason: "Not a char".to_string(),
+                        })?;
+                    if !c.is_alphabetic() {
+                        return Err(CliError::InvalidStartsWith {
+                            value: args[i].clone(),
+                            reason: "Must be a letter".to_string(),
+                        });
+                    }
+                    config.starts_with = Some(c);
+                }
+                "--save-session" => {
+                    i += 1;
+                    config.save_session = Some(session_path(&args, i)?);
+                }
+                "--load-session" => {
+                    i += 1;
+                    config.load_session = Some(session_path(&args, i)?);
+                }
+                "--per-line" => config.per_line = true,
+                "--wc" => config.wc = true,
+                "--rejoin-hyphenation" => config.rejoin_hyphenation = true,
+                "--ocr-cleanup" => config.ocr_cleanup = true,
+                "--recase" => config.recase = true,
+                "--keep-unicode-punct" => config.keep_unicode_punct = true,
+                "--expand-contractions" => config.expand_contractions = true,
+                "--keep-numerals" => config.keep_numerals = true,
+                "--glossary" => config.glossary = true,
+                "--recursive" => config.recursive = true,
+                "--memory-report" => config.memory_report = true,
+                "--reverse" => config.reverse = true,
+                "--stopwords" => {
+                    i += 1;
+                    let value = required(&args, i)?;
+                    let list = match stopwords::StopList::builtin(&value) {
+                        Some(list) => list,
+                        None => stopwords::StopList::parse(&read_text(&value)?),
+                    };
+                    config.stopwords = Some(list);
+                }
+                "--sort" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    config.sort = SortOrder::parse(&value).ok_or(CliError::InvalidSort(value))?;
+                }
+                "--sentence-types" => config.sentence_types = true,
+                "--pronouns" => config.pronouns = true,
+                "--temporal" => {
+                    config.temporal.get_or_insert_with(temporal::Lexicon::default);
+                }
+                "--temporal-lexicon" => {
+                    i += 1;
+                    let path = args.get(i).cloned().unwrap_or_default();
+                    config.temporal = Some(temporal::Lexicon::parse(&read_text(&path)?));
+                }
+                "--keywords" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    config.keywords = Some(keywords::Method::parse(&value).ok_or(CliError::InvalidKeywords(value))?);
+                }
+                "--summary" => {
+                    i += 1;
+                    config.summary = Some(parse_number(&args, i)?);
+                }
+                "--format" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    config.format = Format::parse(&value).ok_or(CliError::InvalidFormat(value))?;
+                }
+                "--tokenizer" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    config.tokenizer = Tokenizer::parse(&value).ok_or(CliError::InvalidTokenizer(value))?;
+                }
+                "--token-pattern" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    let pattern = regex::Regex::new(&value).map_err(|e| CliError::InvalidTokenPattern {
+                        value: value.clone(),
+                        reason: e.to_string(),
+                    })?;
+                    config.token_pattern = Some(pattern);
+                }
+                "--case-sensitive" => config.case_sensitive = true,
+                "--ends-with" => {
+                    i += 1;
+                    config.ends_with = Some(required(&args, i)?);
+                }
+                "--contains" => {
+                    i += 1;
+                    config.contains = Some(required(&args, i)?);
+                }
+                "--stem" => config.stem = true,
+                "--chars" => config.chars = true,
+                "--keep-punct" => config.keep_punct = true,
+                "--strip-punct" => config.keep_punct = false,
+                "--match" => {
+                    i += 1;
+                    config.match_pattern = Some(parse_match(&args, i)?);
+                }
+                "--exclude-match" => {
+                    i += 1;
+                    config.exclude_match = Some(parse_match(&args, i)?);
+                }
+                "--resume" => {
+                    i += 1;
+                    config.resume = Some(session_path(&args, i)?);
+                }
+                "--min-count" => {
+                    i += 1;
+                    config.min_count = parse_number(&args, i)?;
+                }
+                "--precision" => {
+                    i += 1;
+                    config.precision = Some(parse_number(&args, i)?);
+                }
+                "--checkpoint" => {
+                    i += 1;
+                    config.checkpoint = Some(parse_number(&args, i)?);
+                }
+                "--hasher" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    config.hasher = HasherKind::parse(&value).ok_or(CliError::InvalidHasher(value))?;
+                }
+                "--ngrams" => {
+                    i += 1;
+                    config.ngrams = Some(parse_number(&args, i)?);
+                }
+                "--jobs" => {
+                    i += 1;
+                    config.jobs = Some(parse_number(&args, i)?);
+                }
+                "--input-format" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    config.input_format =
+                        Some(transcript::InputFormat::parse(&value).ok_or(CliError::InvalidInputFormat(value))?);
+                }
+                "--normalize-numbers" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    config.normalize_numbers =
+                        Some(number_words::NumberStyle::parse(&value).ok_or(CliError::InvalidNumberStyle(value))?);
+                }
+                "--detailed" => config.detailed = true,
+                "--content-words-only" => config.content_words_only = true,
+                "--whitespace" => config.whitespace = true,
+                "--char-pairs" => config.char_pairs = true,
+                "--alphabet" => config.alphabet = true,
+                "--readability" => config.readability = true,
+                "--sentence-stats" => config.sentence_stats = true,
+                "--language-mix" => config.language_mix = true,
+                "--numbers" => config.numbers = true,
+                "--chart" => config.chart = true,
+                "--hapax" => config.hapax = true,
+                "--leaders" => {
+                    i += 1;
+                    config.leaders = Some(parse_number(&args, i)?);
+                }
+                "--length-dist" => config.length_dist = true,
+                "--histogram" => {
+                    config.length_dist = true;
+                    config.histogram = true;
+                }
+                "--dates" => config.dates = true,
+                "--estimate" => config.estimate = true,
+                "--sentiment-arc" => config.sentiment_arc = true,
+                "--export-cooccurrence" => {
+                    i += 1;
+                    config.export_cooccurrence = args.get(i).cloned();
+                }
+                "--cooccur-window" => {
+                    i += 1;
+                    config.cooccur_window = Some(parse_number(&args, i)?);
+                }
+                "--abbreviations" => {
+                    i += 1;
+                    let path = args.get(i).cloned().unwrap_or_default();
+                    config.sentence_rules.add_abbreviations(&read_text(&path)?);
+                }
+                "--bands" => {
+                    i += 1;
+                    let path = args.get(i).cloned().unwrap_or_default();
+                    config.bands = Some(bands::Reference::parse(&read_text(&path)?));
+                }
+                "--exclude" => {
+                    i += 1;
+                    let words = required(&args, i)?;
+                    let words = words.split(',');
+                    config.exclude.extend(words.map(str::trim).filter(|w| !w.is_empty()).map(String::from));
+                }
+                "--exclude-file" => {
+                    i += 1;
+                    let path = args.get(i).cloned().unwrap_or_default();
+                    // One word per line, `#` starting a comment
+                    let list = read_text(&path)?;
+                    let words = list.lines().map(|l| l.split('#').next().unwrap_or("").trim());
+                    config.exclude.extend(words.filter(|w| !w.is_empty()).map(String::from));
+                }
+                "--rank-of" => {
+                    i += 1;
+                    let words = args.get(i).map_or("", String::as_str).split(',');
+                    config.rank_of.extend(words.map(str::trim).filter(|w| !w.is_empty()).map(String::from));
+                }
+                "--export-brevity" => {
+                    i += 1;
+                    config.export_brevity = args.get(i).cloned();
+                }
+                "--weights" => {
+                    i += 1;
+                    let path = args.get(i).cloned().unwrap_or_default();
+                    let parsed = weights::Weights::parse(&read_text(&path)?);
+                    config.weights = Some(parsed.map_err(|line| CliError::InvalidWeights { path, line })?);
+                }
+                "--export-lm" => {
+                    i += 1;
+                    config.export_lm = args.get(i).cloned();
+                }
+                "--export-idf" => {
+                    i += 1;
+                    config.export_idf = args.get(i).cloned();
+                }
+                "--suggest-stopwords" => {
+                    i += 1;
+                    config.suggest_stopwords = Some(parse_number(&args, i)?);
+                }
+                "--export-stopwords" => {
+                    i += 1;
+                    config.export_stopwords = args.get(i).cloned();
+                }
+                "--trace-tokens" => {
+                    i += 1;
+                    config.trace_tokens = Some(parse_number(&args, i)?);
+                }
+                "--export-tokens" => {
+                    i += 1;
+                    config.export_tokens = args.get(i).cloned();
+                }
+                "--lm-order" => {
+                    i += 1;
+                    config.lm_order = Some(parse_number(&args, i)?);
+                }
+                "--sentences" => {
+                    i += 1;
+                    config.sentences = Some(parse_number(&args, i)?);
+                }
+                "--seed" => {
+                    i += 1;
+                    config.seed = Some(parse_number(&args, i)? as u64);
+                }
+                "--sentence-report" => {
+                    i += 1;
+                    config.sentence_report = args.get(i).cloned();
+                }
+                "--newline-boundary" => config.sentence_rules.newline_boundary = true,
+                "--ellipsis-boundary" => config.sentence_rules.ellipsis_boundary = true,
+                "--language" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    config.language =
+                        Some(language::Language::parse(&value).ok_or(CliError::InvalidLanguage(value))?);
+                }
+                "--emit-freq" => {
+                    i += 1;
+                    config.emit_freq = args.get(i).cloned();
+                }
+                "--export-freq" => {
+                    i += 1;
+                    config.export_freq = args.get(i).cloned();
+                }
+                "--redact" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    config.redact = Some(redact::Redactor::parse(&value).map_err(CliError::InvalidRedact)?);
+                }
+                "--top" => {
+                    i += 1;
+                    config.top = Some(parse_number(&args, i)?);
+                }
+                "--phrase" => {
+                    i += 1;
+                    if let Some(phrase) = args.get(i) {
+                        config.phrases.push(phrase.clone());
+                    }
+                }
+                "--dedup-docs" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    match value.parse::<f64>() {
+                        Ok(threshold) if threshold > 0.0 && threshold <= 1.0 => config.dedup_docs = Some(threshold),
+                        _ => return Err(CliError::InvalidThreshold(value)),
+                    }
+                }
+                "--outliers" => {
+                    i += 1;
+                    config.outliers = Some(parse_number(&args, i)?);
+                }
+                "--top-phrases" => {
+                    i += 1;
+                    config.top_phrases = Some(parse_number(&args, i)?);
+                }
+                "--max-phrase-len" => {
+                    i += 1;
+                    config.max_phrase_len = Some(parse_number(&args, i)?);
+                }
+                "--audience" => {
+                    i += 1;
+                    let value = args.get(i).cloned().unwrap_or_default();
+                    config.audience =
+                        Some(Audience::parse(&value).ok_or(CliError::InvalidAudience(value))?);
+                }
+                "--stat" => {
+                    i += 1;
+                    let value = args.get(i).ok_or_else(|| CliError::InvalidStat {
+                        value: "".to_string(),
+                        reason: "Missing value".to_string(),
+                    })?;
+                    for name in value.split(',') {
+                        let stat = Stat::parse(name.trim()).map_err(|reason| CliError::InvalidStat {
+                            value: name.to_string(),
+                            reason,
+                        })?;
+                        config.stats.push(stat);
+                    }
+                }
+                path if !path.starts_with("--") => config.files.push(path.to_string()),
+                _ => {}
+            }
+            i += 1;
+        }
+        // Single letters like "i" and "x" would read as numerals
+        if config.chars {
+            config.keep_numerals = true;
+        }
+        // Letter pairs come from inside the counted words, which `--chars`
+        // has already split into single characters
+        if config.chars && config.char_pairs {
+            return Err(CliError::ConflictingOptions {
+                first: "--char-pairs".to_string(),
+                second: "--chars".to_string(),
+                reason: "use --ngrams 2 to count character pairs".to_string(),
+            });
+        }
+        if config.export_stopwords.is_some() && config.suggest_stopwords.is_none() {
+            config.suggest_stopwords = Some(stopwords::DEFAULT_SUGGESTIONS);
+        }
+        if !config.case_sensitive {
+            config.starts_with = config.starts_with.map(|c| c.to_ascii_lowercase());
+            config.exclude = config.exclude.iter().map(|w| w.to_lowercase()).collect();
+            config.ends_with = config.ends_with.map(|s| s.to_lowercase());
+            config.contains = config.contains.map(|s| s.to_lowercase());
+        }
+        let options: Vec<&str> = args[1..]
+            .iter()
+            .filter(|a| !config.files.contains(a))
+            .map(String::as_str)
+            .collect();
+        config.options_hash = metadata::fnv1a(options.join("\0").as_bytes());
+        // No path reads piped input, as `-` does
+        if config.files.is_empty() && config.load_session.is_none() {
+            if io::stdin().is_terminal() {
+                return Err(CliError::MissingFilePath);
+            }
+            config.files.push(STDIN_PATH.to_string());
+        }
+        let named = std::mem::take(&mut config.files);
+        config.files = inputs::expand(&named, config.recursive)?;
+        config.by_extension = config.files != named && extensions::mixed(&config.files);
+        config.file_path = config.files.join(", ");
+        Ok(config)
+    }
+
+    // The options that change what gets counted, as `--flag value` pairs in
+    // a fixed order. Session and checkpoint files store it, so counts are
+    // never reused under different filters.
+    pub fn counting_options(&self) -> String {
+        let mut options: Vec<String> = Vec::new();
+        let mut flag = |on: bool, name: &str| {
+            if on {
+                options.push(name.to_string());
+            }
+        };
+        flag(self.case_sensitive, "--case-sensitive");
+        flag(self.chars, "--chars");
+        flag(self.keep_punct, "--keep-punct");
+        flag(self.keep_numerals && !self.chars, "--keep-numerals");
+        flag(self.stem, "--stem");
+        flag(self.rejoin_hyphenation, "--rejoin-hyphenation");
+        flag(self.ocr_cleanup, "--ocr-cleanup");
+        flag(self.recase, "--recase");
+        flag(self.keep_unicode_punct, "--keep-unicode-punct");
+        flag(self.expand_contractions, "--expand-contractions");
+        flag(self.tokenizer == Tokenizer::Whitespace, "--tokenizer whitespace");
+        let mut value = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                options.push(format!("{} {}", name, value));
+            }
+        };
+        value("--min-length", (self.min_length > 0).then(|| self.min_length.to_string()));
+        value("--starts-with", self.starts_with.map(String::from));
+        value("--ends-with", self.ends_with.clone());
+        value("--contains", self.contains.clone());
+        value("--ngrams", self.ngrams.filter(|&n| n > 1).map(|n| n.to_string()));
+        value("--language", self.stem.then(|| format!("{:?}", self.language.unwrap_or(language::Language::English))));
+        value("--token-pattern", self.token_pattern.as_ref().map(|p| p.as_str().to_string()));
+        value("--match", self.match_pattern.as_ref().map(|p| p.as_str().to_string()));
+        value("--exclude-match", self.exclude_match.as_ref().map(|p| p.as_str().to_string()));
+        value("--normalize-numbers", self.normalize_numbers.map(|s| format!("{:?}", s)));
+        value("--input-format", self.input_format.map(|f| format!("{:?}", f)));
+        // Word lists by content, so the same list from another path matches
+        let list = |words: &HashSet<String>| {
+            let mut words: Vec<&str> = words.iter().map(String::as_str).collect();
+            words.sort_unstable();
+            format!("{} words #{:016x}", words.len(), metadata::fnv1a(words.join("\n").as_bytes()))
+        };
+        value(
+            "--stopwords",
+            self.stopwords.as_ref().map(|l| match l {
+                stopwords::StopList::English => "en".to_string(),
+                stopwords::StopList::Custom(words) => list(words),
+            }),
+        );
+        value("--exclude", (!self.exclude.is_empty()).then(|| list(&self.exclude)));
+        options.join(" ")
+    }
+}
+
+fn parse_match(args: &[String], i: usize) -> Result<regex::Regex, CliError> {
+    let value = args.get(i).cloned().unwrap_or_default();
+    regex::Regex::new(&value).map_err(|e| CliError::InvalidMatch {
+        flag: args[i - 1].clone(),
+        value,
+        reason: e.to_string(),
+    })
+}
+
+fn parse_number(args: &[String], i: usize) -> Result<usize, CliError> {
+    let value = args.get(i).cloned().unwrap_or_default();
+    value.parse().map_err(|_| CliError::InvalidNumber {
+        flag: args[i - 1].clone(),
+        value,
+    })
+}
+
+// The value after the flag at `i - 1`
+fn required(args: &[String], i: usize) -> Result<String, CliError> {
+    args.get(i).cloned().ok_or_else(|| CliError::MissingValue(args[i - 1].clone()))
+}
+
+fn session_path(args: &[String], i: usize) -> Result<String, CliError> {
+    args.get(i).cloned().ok_or_else(|| CliError::SessionError {
+        path: "".to_string(),
+        reason: "Missing value".to_string(),
+    })
+}
+
+// Where a token sits in the source: 1-based index in the token stream and line
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Position {
+    pub token: usize,
+    pub line: usize,
+}
+
+// "token:line", right-aligned to the width, without an intermediate String
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = |n: usize| n.checked_ilog10().map_or(1, |d| d as usize + 1);
+        let len = digits(self.token) + 1 + digits(self.line);
+        for _ in len..f.width().unwrap_or(0) {
+            f.write_str(" ")?;
+        }
+        write!(f, "{}:{}", self.token, self.line)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Occurrences {
+    pub first: Position,
+    pub last: Position,
+}
+
+// Counted state of one analysis; everything in the report derives from it
+#[derive(Default)]
+pub struct AnalysisResult {
+    pub freq: HashMap<String, u32, WordState>,
+    pub positions: HashMap<String, Occurrences, WordState>,
+    pub sum_length: usize,
+    // Only measured with --whitespace, and not kept in session files
+    pub whitespace: Option<whitespace::WhitespaceStats>,
+    // Only measured with --readability or a readability check
+    pub readability: Option<readability::Readability>,
+    pub phrases: Vec<(String, u32)>,
+    pub dates: Option<dates::DateStats>,
+    pub sentiment: Option<sentiment::SentimentArc>,
+    pub estimate: Option<estimate::TokenEstimate>,
+    // Roman numerals and ordinals left out of the word counts
+    pub numerals: Option<numerals::NumeralCounts>,
+    pub transcript: Option<transcript::Transcript>,
+    pub keywords: Option<keywords::Keywords>,
+    pub summary: Vec<String>,
+    pub top_phrases: Vec<(String, u32)>,
+    pub glossary: Option<Vec<glossary::Entry>>,
+    pub sentence_types: Option<sentence_types::SentenceTypes>,
+    pub alphabet: Option<chars::Coverage>,
+    pub sentence_stats: Option<sentence::SentenceStats>,
+    pub language_mix: Option<language::Mix>,
+    // With --stem, each stem's surface forms and their counts
+    pub variants: Option<stem::Variants>,
+    // Lines and paragraphs; not kept in session or checkpoint files
+    pub structure: Option<structure::Structure>,
+    pub pronouns: Option<pronouns::PronounProfile>,
+    pub temporal: Option<temporal::Orientation>,
+    pub stopword_suggestions: Option<stopwords::Suggestions>,
+    // Per input file, for `--export-idf` and `--suggest-stopwords`
+    pub document_frequencies: Option<idf::DocumentFrequencies>,
+    pub token_trace: Option<Vec<trace::Traced>>,
+    // Moving-average type-token ratio, with `--stat diversity`
+    pub mattr: Option<f64>,
+    // Hash of the raw input lines when counted from the files
+    pub input_hash: Option<u64>,
+    // Each input in the order given; not kept in session files
+    pub files: Vec<FileStats>,
+}
+
+// How one input went into the counts
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileStatus {
+    Counted,
+    // Only whitespace: an error for a single input, otherwise a zero-count
+    // entry and a warning
+    Empty,
+    // Left out by `--dedup-docs` as a near copy of an earlier input
+    Duplicate { of: String, similarity: f64 },
+}
+
+pub struct FileStats {
+    pub path: String,
+    pub status: FileStatus,
+    pub tokens: u32,
+    pub types: usize,
+    pub sum_length: usize,
+    pub hapax: usize,
+    // Flesch-Kincaid grade of the input's own text, for `--format table`
+    pub grade: Option<f64>,
+    // The input's own word counts, when a per-file section compares them
+    pub freq: Option<HashMap<String, u32>>,
+}
+
+impl FileStats {
+    // The entry for an input counted into `part`
+    pub fn new(path: &str, empty: bool, part: &AnalysisResult, config: &Config) -> FileStats {
+        let keep_freq = config.outliers.is_some() || config.by_extension;
+        FileStats {
+            path: path.to_string(),
+            status: if empty { FileStatus::Empty } else { FileStatus::Counted },
+            tokens: part.total_words(),
+            types: part.freq.len(),
+            sum_length: part.sum_length,
+            hapax: part.freq.values().filter(|&&count| count == 1).count(),
+            grade: None,
+            freq: keep_freq.then(|| part.freq.iter().map(|(w, &c)| (w.clone(), c)).collect()),
+        }
+    }
+}
+
+impl AnalysisResult {
+    pub fn with_hasher(kind: HasherKind) -> AnalysisResult {
+        let state = WordState::new(kind);
+        AnalysisResult {
+            freq: HashMap::with_hasher(state.clone()),
+            positions: HashMap::with_hasher(state),
+            ..AnalysisResult::default()
+        }
+    }
+
+    pub fn total_words(&self) -> u32 {
+        self.freq.values().sum()
+    }
+
+    // Counts one token
+    fn add(&mut self, position: Position, word: String) {
+        *self.freq.entry(word.clone()).or_insert(0) += 1;
+        self.sum_length += word_length(&word);
+        self.positions
+            .entry(word)
+            .and_modify(|o| o.last = position)
+            .or_insert(Occurrences { first: position, last: position });
+    }
+
+    // Adds the counts of a later stretch of input, whose positions start
+    // after `offset` (the lines and raw tokens that came before it)
+    pub fn merge(&mut self, part: AnalysisResult, offset: Position) {
+        let shift = |p: Position| Position {
+            token: p.token + offset.token,
+            line: p.line + offset.line,
+        };
+        self.sum_length += part.sum_length;
+        for (word, count) in part.freq {
+            *self.freq.entry(word).or_insert(0) += count;
+        }
+        for (word, o) in part.positions {
+            self.positions
+                .entry(word)
+                .and_modify(|t| t.last = shift(o.last))
+                .or_insert(Occurrences { first: shift(o.first), last: shift(o.last) });
+        }
+        if let Some(numerals) = part.numerals {
+            self.numerals.get_or_insert_with(Default::default).merge(numerals);
+        }
+        if let Some(variants) = part.variants {
+            let merged = self.variants.get_or_insert_with(HashMap::new);
+            for (stem, forms) in variants {
+                let entry = merged.entry(stem).or_default();
+                for (form, count) in forms {
+                    *entry.entry(form).or_insert(0) += count;
+                }
+            }
+        }
+        if let Some(structure) = part.structure {
+            self.structure.get_or_insert_with(structure::Structure::new).merge(structure);
+        }
+        if let Some(hash) = part.input_hash {
+            let before = self.input_hash.unwrap_or(0);
+            self.input_hash = Some(before.wrapping_add(metadata::shift(hash, offset.line)));
+        }
+    }
+
+    // Words by descending count, ties broken alphabetically
+    pub fn ranked(&self) -> Vec<(&String, u32)> {
+        let mut words: Vec<(&String, u32)> = self.freq.iter().map(|(w, &c)| (w, c)).collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        words
+    }
+
+    // `ranked` without the words counted fewer than `min_count` times
+    pub fn reported(&self, min_count: usize) -> Vec<(&String, u32)> {
+        let mut words = self.ranked();
+        words.retain(|&(_, count)| count as usize >= min_count);
+        words
+    }
+}
+
+// One input as read, before preprocessing
+pub struct Document {
+    pub path: String,
+    pub text: String,
+}
+
+// Every input, in the order given
+pub fn read_documents(paths: &[String], jobs: Option<usize>) -> Result<Vec<Document>, CliError> {
+    let texts = match jobs.filter(|&n| n > 1 && paths.len() > 1) {
+        Some(jobs) => reader::read_parallel(paths, jobs)?,
+        None => paths.iter().map(|path| read_text(path)).collect::<Result<_, _>>()?,
+    };
+    Ok(paths
+        .iter()
+        .zip(texts)
+        .map(|(path, text)| Document { path: path.clone(), text })
+        .collect())
+}
+
+// All inputs as one text, each file ending in a line break so words and
+// lines never run together across files
+pub fn read_inputs(paths: &[String], jobs: Option<usize>) -> Result<String, CliError> {
+    if let [path] = paths {
+        return read_text(path);
+    }
+    let mut text = String::new();
+    for document in read_documents(paths, jobs)? {
+        push_document(&mut text, &document.text);
+    }
+    Ok(text)
+}
+
+fn push_document(text: &mut String, document: &str) {
+    text.push_str(document);
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+// Gzip input (by its magic bytes, not the file name) is decompressed on the
+// fly; `-` reads standard input
+pub fn read_text(path: &str) -> Result<String, CliError> {
+    let bytes = if path == STDIN_PATH {
+        let mut bytes = Vec::new();
+        io::stdin().lock().read_to_end(&mut bytes).map(|_| bytes)
+    } else {
+        fs::read(path)
+    };
+    bytes.and_then(decode).map_err(|e| read_error(path, e))
+}
+
+// A line reader over one input, decompressing gzip and decoding like
+// `read_text`. UTF-8 streams; other encodings are decoded whole first.
+pub fn open(path: &str) -> Result<Box<dyn BufRead + Send>, CliError> {
+    let opened: io::Result<Box<dyn BufRead + Send>> = if path == STDIN_PATH {
+        Ok(Box::new(BufReader::new(io::stdin())))
+    } else {
+        File::open(path).map(|f| Box::new(BufReader::new(f)) as Box<dyn BufRead + Send>)
+    };
+    let mut reader = opened.map_err(|e| read_error(path, e))?;
+    let magic = reader.fill_buf().map_err(|e| read_error(path, e))?;
+    if magic.starts_with(&[0x1f, 0x8b]) {
+        reader = Box::new(BufReader::new(MultiGzDecoder::new(reader)));
+    }
+    let head = reader.fill_buf().map_err(|e| read_error(path, e))?;
+    match encoding::detect(head) {
+        encoding::Encoding::Utf8 => {}
+        encoding::Encoding::Utf8Bom => reader.consume(3),
+        _ => {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).map_err(|e| read_error(path, e))?;
+            let text = encoding::decode(bytes).map_err(|e| read_error(path, e))?;
+            reader = Box::new(io::Cursor::new(text.into_bytes()));
+        }
+    }
+    Ok(reader)
+}
+
+pub(crate) fn read_error(path: &str, e: io::Error) -> CliError {
+    match e.kind() {
+        io::ErrorKind::NotFound => CliError::FileNotFound(path.to_string()),
+        io::ErrorKind::PermissionDenied => CliError::FileReadPermission(path.to_string()),
+        _ => CliError::FileReadError {
+            path: path.to_string(),
+            reason: e.to_string(),
+        },
+    }
+}
+
+fn decode(bytes: Vec<u8>) -> io::Result<String> {
+    let bytes = if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut raw = Vec::new();
+        MultiGzDecoder::new(bytes.as_slice()).read_to_end(&mut raw)?;
+        raw
+    } else {
+        bytes
+    };
+    encoding::decode(bytes)
+}
+
+// Counted tokens in document order, after normalization and filters
+pub fn tokens<'a>(text: &'a str, config: &'a Config) -> impl Iterator<Item = (Position, String)> + 'a {
+    text.lines()
+        .enumerate()
+        .flat_map(move |(line, content)| line_words(content, config).map(move |w| (line + 1, w)))
+        .enumerate()
+        .filter_map(|(token, (line, (w, numeral)))| {
+            normalize(w, numeral, config).map(|w| (Position { token: token + 1, line }, w))
+        })
+}
+
+// Raw words of one line, each with whether `--keep-numerals` is off and it
+// reads as a numeral there
+pub(crate) fn line_words<'a>(content: &'a str, config: &'a Config) -> impl Iterator<Item = (&'a str, bool)> + 'a {
+    let words: Vec<&str> = words(content, config).collect();
+    let numerals = match config.keep_numerals {
+        true => vec![false; words.len()],
+        false => numerals::classes(&words).into_iter().map(|c| c.is_some()).collect(),
+    };
+    words.into_iter().zip(numerals)
+}
+
+// Raw words of one line, before normalization; every one takes a token index
+pub(crate) fn words<'a>(content: &'a str, config: &'a Config) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+    // `--chars`: every visible character (grapheme cluster) is a token
+    if config.chars {
+        return Box::new(content.graphemes(true).filter(|g| !g.chars().all(char::is_whitespace)));
+    }
+    match &config.token_pattern {
+        Some(pattern) => Box::new(pattern.find_iter(content).map(|m| m.as_str())),
+        // Word boundaries would drop the punctuation `--keep-punct` keeps
+        None if config.keep_punct => Tokenizer::Whitespace.split(content),
+        None => config.tokenizer.split(content),
+    }
+}
+
+// Length in grapheme clusters, so "café" is 4 however the é is encoded and
+// an emoji with modifiers is 1
+pub fn word_length(word: &str) -> usize {
+    word.graphemes(true).count()
+}
+
+// The counted form of a raw word, or None when a filter drops it
+fn normalize(raw: &str, numeral: bool, config: &Config) -> Option<String> {
+    surface(raw, numeral, config).map(|w| if config.stem { stem::stem(&w, config.language) } else { w })
+}
+
+// The word as filtered and case-folded, before `--stem`
+fn surface(raw: &str, numeral: bool, config: &Config) -> Option<String> {
+    checked(raw, numeral, config).ok()
+}
+
+// "word." and "word" are one word; inner marks as in "don't" stay
+fn trimmed<'w>(raw: &'w str, config: &Config) -> &'w str {
+    match config.keep_punct || config.chars {
+        true => raw,
+        false => raw.trim_matches(|c: char| !c.is_alphanumeric()),
+    }
+}
+
+fn folded(word: &str, config: &Config) -> String {
+    if config.case_sensitive { word.to_string() } else { word.to_lowercase() } // #3: Map, produces String
+}
+
+// Raw words of a text trimmed and case-folded like counted words, but with
+// no filters applied, so runs of them stay adjacent
+pub(crate) fn plain_words(text: &str, config: &Config) -> Vec<String> {
+    text.lines()
+        .flat_map(|content| words(content, config))
+        .map(|raw| folded(trimmed(raw, config), config))
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+// A word filter and the option it comes from
+type Filter<'f> = (&'static str, &'f dyn Fn(&String) -> bool);
+
+// `surface`, or the name of the step that dropped the word
+pub(crate) fn checked<'a>(raw: &str, numeral: bool, config: &'a Config) -> Result<String, &'static str> {
+    // Curried closures (#7: Currying, #10: Closure)
+    let min_filter = |min_len: usize| move |word: &String| word_length(word) > min_len;
+    let starts_filter = |c: Option<char>| move |word: &String| {
+        c.is_none_or(|c| {
+            word.chars()
+                .next()
+                .is_some_and(|first| first == c)
+        })
+    };
+    let stop_filter = |word: &String| {
+        let stopword = |l: &stopwords::StopList| match config.case_sensitive {
+            true => l.contains(&word.to_lowercase()),
+            false => l.contains(word),
+        };
+        config.chars || !config.stopwords.as_ref().is_some_and(stopword)
+    };
+    let ends_filter = |suffix: Option<&'a str>| move |word: &String| suffix.is_none_or(|s| word.ends_with(s));
+    let contains_filter = |part: Option<&'a str>| move |word: &String| part.is_none_or(|p| word.contains(p));
+    let exclude_filter = |word: &String| !config.exclude.contains(word);
+    let match_filter = |word: &String| config.match_pattern.as_ref().is_none_or(|p| p.is_match(word));
+    let exclude_match_filter = |word: &String| config.exclude_match.as_ref().is_none_or(|p| !p.is_match(word));
+    // In order, each named for the trace (#5: Function Composition)
+    let combined_filter: [Filter; 8] = [
+        ("--min-length", &min_filter(config.min_length)),
+        ("--starts-with", &starts_filter(config.starts_with)),
+        ("--ends-with", &ends_filter(config.ends_with.as_deref())),
+        ("--contains", &contains_filter(config.contains.as_deref())),
+        ("stopwords", &stop_filter),
+        ("--exclude", &exclude_filter),
+        ("--match", &match_filter),
+        ("--exclude-match", &exclude_match_filter),
+    ];
+
+    let raw = trimmed(raw, config);
+    if numeral {
+        return Err("numeral");
+    }
+    let word = folded(raw, config);
+    if word.is_empty() {
+        return Err("punctuation only");
+    }
+    match combined_filter.iter().find(|(_, keep)| !keep(&word)) {
+        Some(&(name, _)) => Err(name),
+        None => Ok(word),
+    }
+}
+
+// The inputs as they are counted: each through the preprocessing passes
+// and, with `--input-format transcript`, with the speaker labels taken out,
+// then joined into one text
+pub struct Prepared<'a> {
+    pub text: Cow<'a, str>,
+    // Path and byte range in `text` of each input; n-grams and paragraphs
+    // are counted within one input, never across two
+    pub documents: Vec<(String, Range<usize>)>,
+    // Speaker and text of each turn, for transcripts
+    pub turns: Option<Vec<(String, String)>>,
+    // Of the raw lines as read
+    pub input_hash: u64,
+    // Inputs left out of `text`, with their place among the inputs
+    pub skipped: Vec<(usize, String, FileStatus)>,
+}
+
+// A single input
+pub fn prepare<'a>(text: &'a str, config: &Config) -> Prepared<'a> {
+    let input_hash = metadata::text_hash(text);
+    let text = preprocess::apply(text, config);
+    let (text, turns) = match config.input_format {
+        Some(transcript::InputFormat::Transcript) => {
+            let (content, turns) = transcript::parse(&text);
+            (Cow::Owned(content), Some(turns))
+        }
+        _ => (text, None),
+    };
+    let documents = vec![(STDIN_PATH.to_string(), 0..text.len())];
+    Prepared { text, documents, turns, input_hash, skipped: Vec::new() }
+}
+
+pub fn prepare_documents<'a>(documents: &'a [Document], config: &Config) -> Prepared<'a> {
+    if let [document] = documents {
+        let mut prepared = prepare(&document.text, config);
+        prepared.documents[0].0 = document.path.clone();
+        return prepared;
+    }
+    let mut joined = Prepared {
+        text: Cow::Owned(String::new()),
+        documents: Vec::with_capacity(documents.len()),
+        turns: None,
+        input_hash: 0,
+        skipped: Vec::new(),
+    };
+    let prepared: Vec<Prepared> = documents.iter().map(|d| prepare(&d.text, config)).collect();
+    let duplicates = match config.dedup_docs {
+        Some(threshold) => {
+            let texts: Vec<&str> = prepared.iter().map(|p| p.text.as_ref()).collect();
+            dedup::find(&texts, config, threshold)
+        }
+        None => vec![None; documents.len()],
+    };
+    let mut text = String::new();
+    let mut lines = 0;
+    for (index, (document, prepared)) in documents.iter().zip(prepared).enumerate() {
+        joined.input_hash = joined.input_hash.wrapping_add(metadata::shift(prepared.input_hash, lines));
+        lines += document.text.lines().count();
+        if let Some((of, similarity)) = duplicates[index] {
+            let of = documents[of].path.clone();
+            joined.skipped.push((index, document.path.clone(), FileStatus::Duplicate { of, similarity }));
+            continue;
+        }
+        let start = text.len();
+        push_document(&mut text, &prepared.text);
+        joined.documents.push((document.path.clone(), start..text.len()));
+        if let Some(turns) = prepared.turns {
+            joined.turns.get_or_insert_with(Vec::new).extend(turns);
+        }
+    }
+    joined.text = Cow::Owned(text);
+    joined
+}
+
+// Analyzes a raw input as the command line does
+pub fn analyze(text: &str, config: &Config) -> AnalysisResult {
+    analyze_prepared(&prepare(text, config), config)
+}
+
+pub fn analyze_prepared(prepared: &Prepared, config: &Config) -> AnalysisResult {
+    let text: &str = &prepared.text;
+    // Count frequencies, positions and lengths (#11: Functional Programming, #12: Lazy Evaluation)
+    let documents = prepared.documents.iter().map(|(path, range)| (path.as_str(), &text[range.clone()]));
+    let mut analysis = pipeline::count_documents(documents, config);
+    analysis.input_hash = Some(prepared.input_hash);
+    if config.format == Format::Table {
+        for (file, (_, range)) in analysis.files.iter_mut().zip(&prepared.documents) {
+            if file.status == FileStatus::Counted {
+                file.grade = Some(readability::measure(&text[range.clone()], config).grade());
+            }
+        }
+    }
+    for (index, path, status) in &prepared.skipped {
+        let mut file = FileStats::new(path, false, &AnalysisResult::default(), config);
+        file.status = status.clone();
+        analysis.files.insert(*index, file);
+    }
+    if let Some(turns) = &prepared.turns {
+        analysis.transcript = Some(transcript::analyze(turns, config));
+    }
+
+    if config.whitespace {
+        analysis.whitespace = Some(whitespace::measure(text));
+    }
+    if config.readability || config.audience.is_some() {
+        analysis.readability = Some(readability::measure(text, config));
+    }
+    if !config.phrases.is_empty() {
+        analysis.phrases = phrases::count(text, config);
+    }
+    if let Some(count) = config.top_phrases {
+        let max_len = config.max_phrase_len.unwrap_or(phrases::DEFAULT_MAX_LEN);
+        analysis.top_phrases = phrases::top(text, count, max_len);
+    }
+    if config.dates {
+        analysis.dates = Some(dates::extract(text));
+    }
+    if config.sentiment_arc {
+        analysis.sentiment = Some(sentiment::arc(text));
+    }
+    if config.estimate {
+        analysis.estimate = Some(estimate::estimate(text));
+    }
+    if !config.keep_numerals {
+        analysis.numerals = Some(numerals::count(text));
+    }
+    if let Some(method) = config.keywords {
+        analysis.keywords = Some(keywords::extract(text, method));
+    }
+    if config.language_mix {
+        analysis.language_mix = Some(language::mix(text));
+    }
+    if config.sentence_stats {
+        analysis.sentence_stats = Some(sentence::stats(text, &config.sentence_rules));
+    }
+    if config.alphabet {
+        analysis.alphabet = Some(chars::coverage(text, config));
+    }
+    if config.sentence_types {
+        analysis.sentence_types = Some(sentence_types::classify(text, config));
+    }
+    if config.pronouns {
+        analysis.pronouns = Some(pronouns::profile(text));
+    }
+    if let Some(lexicon) = &config.temporal {
+        analysis.temporal = Some(temporal::measure(text, lexicon));
+    }
+    if config.glossary {
+        analysis.glossary = Some(glossary::extract(text));
+    }
+    if config.stats.contains(&Stat::Diversity) {
+        analysis.mattr = diversity::mattr(text, config);
+    }
+    if let Some(n) = config.trace_tokens {
+        analysis.token_trace = Some(trace::trace(text, config, n));
+    }
+    if let Some(count) = config.summary {
+        analysis.summary = keywords::summarize(text, &config.sentence_rules, count)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+    }
+    if config.export_idf.is_some() || config.suggest_stopwords.is_some() {
+        let df = idf::document_frequencies(prepared, config);
+        if let Some(n) = config.suggest_stopwords {
+            analysis.stopword_suggestions = Some(stopwords::suggest(&analysis, &df, n));
+        }
+        analysis.document_frequencies = Some(df);
+    }
+    analysis
+}
+
+// Exit codes are a stable contract for wrapper scripts: a code is never
+// reused or renumbered, and new errors take the next free number. Printed
+// by `text_analyzer exit-codes`; keep in step with the match below.
+pub const EXIT_CODES: &[(i32, &str, &str)] = &[
+    (0, "Success", "Analysis completed"),
+    (1, "MissingFilePath", "No input path given and stdin is a terminal"),
+    (2, "InvalidMinLength", "--min-length is not a valid length"),
+    (3, "InvalidStartsWith", "--starts-with is not a valid letter"),
+    (4, "FileNotFound", "An input file does not exist"),
+    (5, "FileReadPermission", "An input file cannot be read"),
+    (6, "FileReadError", "An input could not be read or decoded"),
+    (7, "EmptyFile", "A single input holds only whitespace"),
+    (8, "SessionError", "A session or checkpoint file is invalid or incompatible"),
+    (9, "InvalidStat", "Unknown --stat name"),
+    (10, "InvalidAudience", "Unknown --audience"),
+    (11, "AudienceNotMet", "The text is above the --audience grade band"),
+    (12, "InvalidNumber", "A numeric option is not a number"),
+    (13, "ExportError", "An export file could not be written"),
+    (14, "InvalidRedact", "Unknown --redact category"),
+    (15, "InvalidFreqFile", "A frequency file line is not word<TAB>count"),
+    (16, "InvalidLanguage", "Unknown --language"),
+    (17, "InvalidNumberStyle", "Unknown --normalize-numbers style"),
+    (18, "InvalidInputFormat", "Unknown --input-format"),
+    (19, "InvalidKeywords", "Unknown --keywords method"),
+    (20, "InvalidFormat", "Unknown --format"),
+    (21, "InvalidTokenPattern", "--token-pattern is not a valid regex"),
+    (22, "InvalidHasher", "Unknown --hasher"),
+    (23, "InvalidSort", "Unknown --sort order"),
+    (24, "InvalidMatch", "--match or --exclude-match is not a valid regex"),
+    (25, "InvalidTokenizer", "Unknown --tokenizer"),
+    (26, "InvalidWeights", "A --weights line is not word,weight"),
+    (27, "ConflictingOptions", "Two options cannot be used together"),
+    (28, "InvalidThreshold", "--dedup-docs is not a similarity in (0, 1]"),
+    (29, "MissingValue", "An option was given without its value"),
+];
+
+// Error to exit code (#16: Pattern Matching)
+impl From<CliError> for i32 {
+    fn from(err: CliError) -> i32 {
+        match err {
+            CliError::MissingFilePath => {
+                eprintln!("Error: Missing file path.");
+                1
+            }
+            CliError::InvalidMinLength { value, reason } => {
+                eprintln!("Error: Invalid --min-length '{}': {}", value, reason);
+                2
+            }
+            CliError::InvalidStartsWith { value, reason } => {
+                eprintln!("Error: Invalid --starts-with '{}': {}", value, reason);
+                3
+            }
+            CliError::FileNotFound(path) => {
+                eprintln!("Error: File '{}' not found.", path);
+                4
+            }
+            CliError::FileReadPermission(path) => {
+                eprintln!("Error: Permission denied reading '{}'.", path);
+                5
+            }
+            CliError::FileReadError { path, reason } => {
+                eprintln!("Error: Failed to read '{}': {}", path, reason);
+                6
+            }
+            CliError::EmptyFile => {
+                eprintln!("Error: File is empty.");
+                7
+            }
+            CliError::SessionError { path, reason } => {
+                eprintln!("Error: Session file '{}': {}", path, reason);
+                8
+            }
+            CliError::InvalidStat { value, reason } => {
+                eprintln!("Error: Invalid --stat '{}': {}", value, reason);
+                9
+            }
+            CliError::InvalidAudience(value) => {
+                eprintln!("Error: Invalid --audience '{}': Expected grade8, college or expert", value);
+                10
+            }
+            CliError::AudienceNotMet { audience, grade } => {
+                eprintln!(
+                    "Error: Grade level {:.1} is above the {} target (max {:.1}).",
+                    grade,
+                    audience.name(),
+                    audience.max_grade()
+                );
+                11
+            }
+            CliError::InvalidNumber { flag, value } => {
+                eprintln!("Error: Invalid {} '{}': Not a number", flag, value);
+                12
+            }
+            CliError::ExportError { path, reason } => {
+                eprintln!("Error: Failed to write '{}': {}", path, reason);
+                13
+            }
+            CliError::InvalidRedact(reason) => {
+                eprintln!("Error: Invalid --redact: {}", reason);
+                14
+            }
+            CliError::InvalidFreqFile { path, line } => {
+                eprintln!("Error: '{}' line {}: expected 'word<TAB>count'", path, line);
+                15
+            }
+            CliError::InvalidLanguage(value) => {
+                eprintln!("Error: Invalid --language '{}': Expected en, es, de or fr", value);
+                16
+            }
+            CliError::InvalidNumberStyle(value) => {
+                eprintln!("Error: Invalid --normalize-numbers '{}': Expected words or digits", value);
+                17
+            }
+            CliError::InvalidInputFormat(value) => {
+                eprintln!("Error: Invalid --input-format '{}': Expected text or transcript", value);
+                18
+            }
+            CliError::InvalidKeywords(value) => {
+                eprintln!("Error: Invalid --keywords '{}': Expected rake or textrank", value);
+                19
+            }
+            CliError::InvalidFormat(value) => {
+                eprintln!("Error: Invalid --format '{}': Expected text, json or table", value);
+                20
+            }
+            CliError::InvalidTokenPattern { value, reason } => {
+                eprintln!("Error: Invalid --token-pattern '{}': {}", value, reason);
+                21
+            }
+            CliError::InvalidHasher(value) => {
+                eprintln!("Error: Invalid --hasher '{}': Expected fx or siphash", value);
+                22
+            }
+            CliError::InvalidSort(value) => {
+                eprintln!("Error: Invalid --sort '{}': Expected count, alpha or length", value);
+                23
+            }
+            CliError::InvalidMatch { flag, value, reason } => {
+                eprintln!("Error: Invalid {} '{}': {}", flag, value, reason);
+                24
+            }
+            CliError::InvalidTokenizer(value) => {
+                eprintln!("Error: Invalid --tokenizer '{}': Expected unicode or whitespace", value);
+                25
+            }
+            CliError::InvalidWeights { path, line } => {
+                eprintln!("Error: '{}' line {}: expected 'word,weight'", path, line);
+                26
+            }
+            CliError::ConflictingOptions { first, second, reason } => {
+                eprintln!("Error: {} cannot be used with {}: {}", first, second, reason);
+                27
+            }
+            CliError::InvalidThreshold(value) => {
+                eprintln!("Error: Invalid --dedup-docs '{}': Expected a similarity above 0 and at most 1", value);
+                28
+            }
+            CliError::MissingValue(flag) => {
+                eprintln!("Error: {} needs a value", flag);
+                29
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analyze_applies_preprocessing() {
+        let config = Config {
+            rejoin_hyphenation: true,
+            ..Config::default()
+        };
+        let analysis = analyze("a long hyphen-\nated word\n", &config);
+        assert_eq!(analysis.freq.get("hyphenated"), Some(&1));
+        assert_eq!(analysis.freq.get("ated"), None);
+    }
+
+    #[test]
+    fn analyze_parses_transcripts() {
+        let config = Config {
+            input_format: Some(transcript::InputFormat::Transcript),
+            ..Config::default()
+        };
+        let analysis = analyze("Alice: hello there\nBob: hello\n", &config);
+        assert_eq!(analysis.freq.get("alice"), None);
+        assert_eq!(analysis.freq.get("hello"), Some(&2));
+        assert_eq!(analysis.transcript.map(|t| t.speakers.len()), Some(2));
+    }
+
+    #[test]
+    fn ngrams_stay_within_a_document() {
+        let config = Config {
+            ngrams: Some(2),
+            ..Config::default()
+        };
+        let documents = ["x y", "z"].map(|text| Document { path: text.to_string(), text: text.to_string() });
+        let analysis = analyze_prepared(&prepare_documents(&documents, &config), &config);
+        assert_eq!(analysis.ranked(), [(&"x y".to_string(), 1)]);
+        assert_eq!(analysis.positions["x y"].first, Position { token: 1, line: 1 });
+    }
+
+    #[test]
+    fn char_pairs_need_words() {
+        let args = ["text_analyzer", "-", "--chars", "--char-pairs"].map(String::from).to_vec();
+        assert!(matches!(Config::new(args), Err(CliError::ConflictingOptions { .. })));
+    }
+}
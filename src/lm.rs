@@ -0,0 +1,106 @@
+// N-gram language model export, built from per-sentence n-gram counts over
+// the counted tokens (with <s> and </s> markers). Paths ending in `.json`
+// get the raw conditional counts; anything else gets an ARPA file with
+// unsmoothed maximum-likelihood probabilities.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::redact::shown;
+use crate::{Config, json, sentence, tokens};
+
+const DEFAULT_ORDER: usize = 3;
+
+pub struct NgramModel {
+    pub order: usize,
+    // counts[n - 1] holds the n-gram counts
+    pub counts: Vec<HashMap<Vec<String>, u32>>,
+}
+
+impl NgramModel {
+    pub fn build(text: &str, config: &Config) -> NgramModel {
+        let order = config.lm_order.unwrap_or(DEFAULT_ORDER).max(1);
+        let mut counts = vec![HashMap::new(); order];
+        for s in sentence::split(text, &config.sentence_rules) {
+            let words: Vec<String> = std::iter::once("<s>".to_string())
+                .chain(tokens(s, config).map(|(_, w)| shown(config.redact.as_ref(), &w).into_owned()))
+                .chain(std::iter::once("</s>".to_string()))
+                .collect();
+            for (n, table) in counts.iter_mut().enumerate() {
+                for window in words.windows(n + 1) {
+                    *table.entry(window.to_vec()).or_insert(0) += 1;
+                }
+            }
+        }
+        NgramModel { order, counts }
+    }
+
+    fn sorted(&self, n: usize) -> BTreeMap<&Vec<String>, u32> {
+        self.counts[n - 1].iter().map(|(g, &c)| (g, c)).collect()
+    }
+}
+
+pub fn export(path: &str, text: &str, config: &Config) -> io::Result<()> {
+    let model = NgramModel::build(text, config);
+    let mut out = BufWriter::new(File::create(path)?);
+    if path.ends_with(".json") {
+        write_json(&mut out, &model)?;
+    } else {
+        write_arpa(&mut out, &model)?;
+    }
+    out.flush()
+}
+
+fn write_arpa(out: &mut impl Write, model: &NgramModel) -> io::Result<()> {
+    writeln!(out, "\\data\\")?;
+    for n in 1..=model.order {
+        writeln!(out, "ngram {}={}", n, model.counts[n - 1].len())?;
+    }
+    for n in 1..=model.order {
+        // How often each history was followed by a predicted word;
+        // <s> is never predicted, only conditioned on
+        let mut history_counts: HashMap<&[String], u32> = HashMap::new();
+        for (gram, &count) in model.counts[n - 1].iter().filter(|(g, _)| g[n - 1] != "<s>") {
+            *history_counts.entry(&gram[..n - 1]).or_insert(0) += count;
+        }
+
+        writeln!(out, "\n\\{}-grams:", n)?;
+        for (gram, count) in model.sorted(n) {
+            let logprob = match history_counts.get(&gram[..n - 1]) {
+                Some(&total) if gram[n - 1] != "<s>" => (count as f64 / total as f64).log10(),
+                _ => -99.0,
+            };
+            writeln!(out, "{:.6}\t{}", logprob, gram.join(" "))?;
+        }
+    }
+    writeln!(out, "\n\\end\\")
+}
+
+// {"order": 3, "contexts": {"": {"the": 7, ...}, "the": {"dog": 2, ...}}}
+fn write_json(out: &mut impl Write, model: &NgramModel) -> io::Result<()> {
+    let mut contexts: BTreeMap<String, BTreeMap<&str, u32>> = BTreeMap::new();
+    for n in 1..=model.order {
+        for (gram, count) in model.sorted(n) {
+            contexts
+                .entry(gram[..n - 1].join(" "))
+                .or_default()
+                .insert(gram[n - 1].as_str(), count);
+        }
+    }
+    write!(out, "{{\"order\":{},\"contexts\":{{", model.order)?;
+    for (i, (context, next)) in contexts.iter().enumerate() {
+        if i > 0 {
+            write!(out, ",")?;
+        }
+        write!(out, "{}:{{", json::string(context))?;
+        for (j, (word, count)) in next.iter().enumerate() {
+            if j > 0 {
+                write!(out, ",")?;
+            }
+            write!(out, "{}:{}", json::string(word), count)?;
+        }
+        write!(out, "}}")?;
+    }
+    writeln!(out, "}}}}")
+}
@@ -1,192 +1,254 @@
-use std::collections::HashMap;
+// Command-line entry point: subcommands, option checks and output, on top
+// of the text_analyzer library.
+
 use std::env;
-use std::fs;
 use std::process;
+use std::time::SystemTime;
 
-// Enum for error states (#14: Enum, #16: Pattern Matching)
-#[derive(Debug)]
-enum CliError {
-    MissingFilePath,
-    InvalidMinLength { value: String, reason: String },
-    InvalidStartsWith { value: String, reason: String },
-    FileNotFound(String),
-    FileReadPermission(String),
-    FileReadError(String),
-    EmptyFile,
-}
-
-// Builder Pattern for configuration (#1)
-#[derive(Default)]
-struct Config {
-    file_path: String,
-    min_length: usize,
-    starts_with: Option<char>,
-}
+use text_analyzer::report::{Format, Stat};
+use text_analyzer::{
+    CliError, Config, EXIT_CODES, FileStatus, STDIN_PATH, analyze_prepared, brevity, checkpoint, cooccurrence, demo, freq,
+    generate, idf, lm, memory, metadata, per_line, pipeline, prepare_documents, print_json, print_report, read_documents, read_inputs,
+    readability, session, stopwords, table, token_stream, transcript, wc,
+};
 
-impl Config {
-    fn new(args: Vec<String>) -> Result<Self, CliError> {
-        let mut config = Config::default();
-        if args.len() < 2 {
-            return Err(CliError::MissingFilePath);
+// Program logic (#11: Functional Programming)
+fn run() -> Result<(), CliError> {
+    let mut args: Vec<String> = env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("demo") => {
+            demo::run();
+            return Ok(());
         }
-        config.file_path = args[1].clone();
-
-        let mut i = 2;
-        while i < args.len() {
-            match args[i].as_str() {
-                "--min-length" => {
-                    i += 1;
-                    config.min_length = args
-                        .get(i)
-                        .ok_or_else(|| CliError::InvalidMinLength {
-                            value: "".to_string(),
-                            reason: "Missing value".to_string(),
-                        })?
-                        .parse()
-                        .map_err(|_| CliError::InvalidMinLength {
-                            value: args[i].clone(),
-                            reason: "Not a number".to_string(),
-                        })?;
-                }
-                "--starts-with" => {
-                    i += 1;
-                    let c = args
-                        .get(i)
-                        .ok_or_else(|| CliError::InvalidStartsWith {
-                            value: "".to_string(),
-                            reason: "Missing value".to_string(),
-                        })?
-                        .chars()
-                        .next()
-                        .ok_or_else(|| CliError::InvalidStartsWith {
-                            value: args[i].clone(),
-                            reason: "Not a char".to_string(),
-                        })?;
-                    if !c.is_alphabetic() {
-                        return Err(CliError::InvalidStartsWith {
-                            value: args[i].clone(),
-                            reason: "Must be a letter".to_string(),
-                        });
-                    }
-                    config.starts_with = Some(c.to_ascii_lowercase());
-                }
-                _ => {
-                    i += 1;
-                }
+        // Tab-separated code, variant and meaning, one per line
+        Some("exit-codes") => {
+            for (code, name, meaning) in EXIT_CODES {
+                println!("{}\t{}\t{}", code, name, meaning);
+            }
+            return Ok(());
+        }
+        Some("freq-diff") => {
+            let (a, b) = match (args.get(2), args.get(3)) {
+                (Some(a), Some(b)) => (a, b),
+                _ => return Err(CliError::MissingFilePath),
+            };
+            return freq::diff(a, b);
+        }
+        Some("generate") => {
+            args.remove(1);
+            let config = Config::new(args)?;
+            let text = read_inputs(&config.files, config.jobs)?;
+            let model = lm::NgramModel::build(&text, &config);
+            for sentence in generate::sentences(&model, config.sentences, config.seed) {
+                println!("{}", sentence);
             }
+            return Ok(());
         }
-        Ok(config)
+        _ => {}
     }
-}
+    let started = SystemTime::now();
+    let mut config = Config::new(args)?;
 
-// Program logic (#11: Functional Programming)
-fn run() -> Result<(), CliError> {
-    let args: Vec<String> = env::args().collect();
-    let config = Config::new(args)?;
-
-    let text = fs::read_to_string(&config.file_path).map_err(|e| match e.kind() {
-        std::io::ErrorKind::NotFound => CliError::FileNotFound(config.file_path.clone()),
-        std::io::ErrorKind::PermissionDenied => CliError::FileReadPermission(config.file_path.clone()),
-        _ => CliError::FileReadError(e.to_string()),
-    })?;
-    if text.trim().is_empty() {
-        return Err(CliError::EmptyFile);
+    // Options that look at the raw text cannot run from a session file
+    let source_only = [
+        (config.per_line, "--per-line"),
+        (config.wc, "--wc"),
+        (config.audience.is_some(), "--audience"),
+        (config.readability, "--readability"),
+        (config.sentence_stats, "--sentence-stats"),
+        (config.language_mix, "--language-mix"),
+        (!config.phrases.is_empty(), "--phrase"),
+        (config.top_phrases.is_some(), "--top-phrases"),
+        (config.dates, "--dates"),
+        (config.sentiment_arc, "--sentiment-arc"),
+        (config.export_cooccurrence.is_some(), "--export-cooccurrence"),
+        (config.sentence_report.is_some(), "--sentence-report"),
+        (config.export_lm.is_some(), "--export-lm"),
+        (config.export_tokens.is_some(), "--export-tokens"),
+        (config.trace_tokens.is_some(), "--trace-tokens"),
+        (config.stats.contains(&Stat::Diversity), "--stat diversity"),
+        (config.export_idf.is_some(), "--export-idf"),
+        (config.suggest_stopwords.is_some(), "--suggest-stopwords"),
+        (config.estimate, "--estimate"),
+        (config.redact.as_ref().is_some_and(|r| r.needs_source()), "--redact names"),
+        (config.input_format == Some(transcript::InputFormat::Transcript), "--input-format transcript"),
+        (config.keywords.is_some(), "--keywords"),
+        (config.summary.is_some(), "--summary"),
+        (config.glossary, "--glossary"),
+        (config.sentence_types, "--sentence-types"),
+        (config.pronouns, "--pronouns"),
+        (config.temporal.is_some(), "--temporal"),
+        (config.alphabet, "--alphabet"),
+        (config.format == Format::Table, "--format table"),
+        (config.dedup_docs.is_some(), "--dedup-docs"),
+    ];
+    if let (Some(path), Some((_, flag))) = (&config.load_session, source_only.iter().find(|o| o.0)) {
+        return Err(CliError::SessionError {
+            path: path.clone(),
+            reason: format!("{} needs the source text", flag),
+        });
     }
 
-    // Curried closures (#7: Currying, #10: Closure)
-    let min_filter = |min_len: usize| move |word: &String| word.len() > min_len;
-    let starts_filter = |c: Option<char>| move |word: &String| {
-        c.map_or(true, |c| {
-            word.chars()
-                .next()
-                .map_or(false, |first| first.to_ascii_lowercase() == c)
-        })
-    };
-    let combined_filter = |word: &String| {
-        min_filter(config.min_length)(word)
-            && starts_filter(config.starts_with)(word)
-    };
-
-    // Count frequencies and sum lengths (#11: Functional Programming, #12: Lazy Evaluation)
-    let (freq, sum_length): (HashMap<String, u32>, usize) = text
-        .split_whitespace()
-        .map(|w| w.to_lowercase()) // #3: Map, produces String
-        .filter(|w: &String| !w.is_empty())
-        .filter(combined_filter) // #5: Function Composition
-        .fold(
-            (HashMap::new(), 0),
-            |(mut freq, sum_length), word| {
-                *freq.entry(word.clone()).or_insert(0) += 1;
-                (freq, sum_length + word.len())
-            },
-        );
-
-    // Stats (#6: Sum)
-    let total_words: u32 = freq.values().sum();
-    let average_length = if total_words > 0 {
-        (sum_length as f64 / total_words as f64).round() as usize
-    } else {
-        0
-    };
-    let unique_words = freq.len();
-    let most_common = freq
-        .iter()
-        .max_by_key(|&(word, &count)| (count, std::cmp::Reverse(word)));
+    // Checkpointed runs count file by file, so whole-text options cannot join
+    if config.checkpoint.is_some() && config.resume.is_none() {
+        return Err(CliError::SessionError {
+            path: "".to_string(),
+            reason: "--checkpoint needs --resume STATE for the state file".to_string(),
+        });
+    }
+    if let (Some(path), Some((_, flag))) = (&config.resume, source_only.iter().find(|o| o.0)) {
+        return Err(CliError::SessionError {
+            path: path.clone(),
+            reason: format!("{} needs the whole text, not a file-by-file run", flag),
+        });
+    }
 
-    // Output
-    println!("=== Text Analyzer Results ===");
-    println!("File: {}", config.file_path);
-    println!("Filters Applied:");
-    println!("  Minimum length: {}", config.min_length);
-    if let Some(c) = config.starts_with {
-        println!("  Starts with: {}", c);
-    }
-    println!("\nStats:");
-    println!("  Total word count: {}", total_words);
-    println!("  Number of unique words: {}", unique_words);
-    println!("  Average word length: {} chars", average_length);
-    match most_common {
-        Some((word, &count)) => println!("  Most common word: \"{}\" with count {}", word, count),
-        None => println!("  No words found."),
+    // Options that compare the inputs; neither state file keeps per-file counts
+    let per_file = [(config.outliers.is_some(), "--outliers")];
+    let saved = config.load_session.as_ref().or(config.resume.as_ref());
+    if let (Some(path), Some((_, flag))) = (saved, per_file.iter().find(|o| o.0)) {
+        return Err(CliError::SessionError {
+            path: path.clone(),
+            reason: format!("{} needs the counts of each input file", flag),
+        });
     }
 
-    Ok(())
-}
+    // Passes that join lines or look at the whole text, and whitespace
+    // stats, need the files read whole
+    let streamable = !source_only.iter().any(|o| o.0)
+        && !config.whitespace
+        && !config.rejoin_hyphenation
+        && !config.ocr_cleanup
+        && !config.recase;
 
-// Error to exit code (#16: Pattern Matching)
-impl From<CliError> for i32 {
-    fn from(err: CliError) -> i32 {
-        match err {
-            CliError::MissingFilePath => {
-                eprintln!("Error: Missing file path.");
-                1
+    let analysis = match (config.load_session.clone(), config.resume.clone()) {
+        (Some(path), _) => session::load(&path, &mut config).map_err(|e| CliError::SessionError {
+            path,
+            reason: e.to_string(),
+        })?,
+        (None, Some(path)) => checkpoint::run(&path, &config)?,
+        // Counting alone never needs the whole text in memory
+        (None, None) if streamable => pipeline::stream(&config.files, &config)?,
+        (None, None) => {
+            if config.wc {
+                return wc::run(&config.files);
+            }
+            let documents = read_documents(&config.files, config.jobs)?;
+            if let [document] = documents.as_slice()
+                && document.text.trim().is_empty()
+            {
+                return Err(CliError::EmptyFile);
             }
-            CliError::InvalidMinLength { value, reason } => {
-                eprintln!("Error: Invalid --min-length '{}': {}", value, reason);
-                2
+            let prepared = prepare_documents(&documents, &config);
+            if let Some(redactor) = &mut config.redact {
+                redactor.learn_names(&prepared.text, &config.sentence_rules);
             }
-            CliError::InvalidStartsWith { value, reason } => {
-                eprintln!("Error: Invalid --starts-with '{}': {}", value, reason);
-                3
+            if config.per_line {
+                per_line::print_csv(&prepared.text).map_err(|e| CliError::ExportError {
+                    path: STDIN_PATH.to_string(),
+                    reason: e.to_string(),
+                })?;
+                return Ok(());
             }
-            CliError::FileNotFound(path) => {
-                eprintln!("Error: File '{}' not found.", path);
-                4
+            let analysis = analyze_prepared(&prepared, &config);
+            let text: &str = &prepared.text;
+            if let Some(path) = &config.export_cooccurrence {
+                cooccurrence::export(path, text, &config, &analysis).map_err(|e| {
+                    CliError::ExportError {
+                        path: path.clone(),
+                        reason: e.to_string(),
+                    }
+                })?;
             }
-            CliError::FileReadPermission(path) => {
-                eprintln!("Error: Permission denied reading '{}'.", path);
-                5
+            if let Some(path) = &config.export_tokens {
+                token_stream::export(path, text, &config).map_err(|e| CliError::ExportError {
+                    path: path.clone(),
+                    reason: e.to_string(),
+                })?;
             }
-            CliError::FileReadError(e) => {
-                eprintln!("Error: Failed to read file: {}", e);
-                6
+            if let Some(path) = &config.export_lm {
+                lm::export(path, text, &config).map_err(|e| CliError::ExportError {
+                    path: path.clone(),
+                    reason: e.to_string(),
+                })?;
             }
-            CliError::EmptyFile => {
-                eprintln!("Error: File is empty.");
-                7
+            if let Some(path) = &config.sentence_report {
+                readability::export_sentences(path, text, &config).map_err(|e| {
+                    CliError::ExportError {
+                        path: path.clone(),
+                        reason: e.to_string(),
+                    }
+                })?;
             }
+            analysis
+        }
+    };
+
+    // An empty input fails a single-file run; among several it counts as zero
+    match analysis.files.as_slice() {
+        [file] if file.status == FileStatus::Empty => return Err(CliError::EmptyFile),
+        files => {
+            for file in files.iter().filter(|f| f.status == FileStatus::Empty) {
+                eprintln!("Warning: '{}' is empty; counted as zero", file.path);
+            }
+        }
+    }
+
+    if let Some(path) = &config.emit_freq {
+        freq::emit(path, &config, &analysis).map_err(|e| CliError::ExportError {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+    }
+    let meta = metadata::RunMetadata::finish(&config, &analysis, started);
+    if let Some(path) = &config.export_freq {
+        freq::export_csv(path, &config, &analysis, &meta).map_err(|e| CliError::ExportError {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+    }
+    if let (Some(path), Some(frequencies)) = (&config.export_idf, &analysis.document_frequencies) {
+        idf::export(path, &config, frequencies, &meta).map_err(|e| CliError::ExportError {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+    }
+    if let (Some(path), Some(suggestions)) = (&config.export_stopwords, &analysis.stopword_suggestions) {
+        stopwords::export(path, &config, suggestions, &meta).map_err(|e| CliError::ExportError {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+    }
+    if let Some(path) = &config.export_brevity {
+        brevity::export(path, &analysis, &meta).map_err(|e| CliError::ExportError {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+    }
+    if let Some(path) = &config.save_session {
+        session::save(path, &config, &analysis).map_err(|e| CliError::SessionError {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+    }
+
+    match config.format {
+        Format::Text => print_report(&config, &analysis),
+        Format::Json => print_json(&config, &analysis, &meta),
+        Format::Table => table::print(&config, &analysis),
+    }
+    if config.memory_report {
+        memory::print(&analysis);
+    }
+
+    // CI gate: a text above the audience's grade band fails the run
+    if let (Some(audience), Some(r)) = (config.audience, &analysis.readability) {
+        let grade = r.grade();
+        if !audience.accepts(grade) {
+            return Err(CliError::AudienceNotMet { audience, grade });
         }
     }
+    Ok(())
 }
 
 fn main() {
@@ -0,0 +1,39 @@
+// `--memory-report`: an estimate of what the vocabulary costs in memory.
+// Each word is held twice, as a key of the frequency map and of the
+// position map; the table cost follows hashbrown's layout of one slot of
+// key + value plus one control byte per bucket. Printed on stderr so it
+// never mixes into piped output.
+
+use std::mem::size_of;
+
+use crate::{AnalysisResult, Occurrences};
+
+fn table_bytes<V>(capacity: usize) -> usize {
+    // Buckets are the next power of two holding capacity at 7/8 load
+    let buckets = if capacity == 0 { 0 } else { (capacity * 8 / 7).next_power_of_two() };
+    buckets * (size_of::<String>() + size_of::<V>() + 1)
+}
+
+fn kib(bytes: usize) -> String {
+    format!("{:.1} KiB", bytes as f64 / 1024.0)
+}
+
+pub fn print(analysis: &AnalysisResult) {
+    let entries = analysis.freq.len();
+    let key_bytes: usize = analysis.freq.keys().map(String::capacity).sum::<usize>()
+        + analysis.positions.keys().map(String::capacity).sum::<usize>();
+    let freq_table = table_bytes::<u32>(analysis.freq.capacity());
+    let positions_table = table_bytes::<Occurrences>(analysis.positions.capacity());
+    let total = key_bytes + freq_table + positions_table;
+
+    eprintln!("\nMemory (estimated):");
+    eprintln!("  Vocabulary entries: {}", entries);
+    eprintln!("  Key bytes (both maps): {}", kib(key_bytes));
+    eprintln!("  Frequency table: {}", kib(freq_table));
+    eprintln!("  Position table: {}", kib(positions_table));
+    eprintln!(
+        "  Total: {} ({:.0} bytes per entry)",
+        kib(total),
+        total as f64 / entries.max(1) as f64
+    );
+}
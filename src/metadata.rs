@@ -0,0 +1,117 @@
+// Run metadata for machine outputs: tool version, a hash of the options
+// and of the input, and when the run started and how long it took, so a
+// stored report can be traced back to what produced it.
+//
+// The input hash sums each line's FNV-1a hash weighted by its position, so
+// files counted apart (in parallel or streamed) combine into the same value
+// as reading them as one text.
+
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{AnalysisResult, Config};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+pub struct RunMetadata {
+    pub version: &'static str,
+    pub options_hash: u64,
+    // Not known for runs loaded from a session or resumed from a checkpoint
+    pub input_hash: Option<u64>,
+    pub started: SystemTime,
+    pub duration: Duration,
+}
+
+impl RunMetadata {
+    // Closes the run that began at `started`
+    pub fn finish(config: &Config, analysis: &AnalysisResult, started: SystemTime) -> RunMetadata {
+        RunMetadata {
+            version: env!("CARGO_PKG_VERSION"),
+            options_hash: config.options_hash,
+            input_hash: analysis.input_hash,
+            started,
+            duration: started.elapsed().unwrap_or_default(),
+        }
+    }
+
+    // `"metadata": {...}` for the JSON report
+    pub fn json(&self) -> String {
+        let input_hash = self.input_hash.map_or("null".to_string(), |h| format!("\"{:016x}\"", h));
+        format!(
+            "{{\"version\": \"{}\", \"options_hash\": \"{:016x}\", \"input_hash\": {}, \
+             \"started\": \"{}\", \"finished\": \"{}\", \"duration_ms\": {}}}",
+            self.version,
+            self.options_hash,
+            input_hash,
+            timestamp(self.started),
+            timestamp(self.started + self.duration),
+            self.duration.as_millis()
+        )
+    }
+
+    // `# key: value` lines ahead of a CSV header
+    pub fn write_comments(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "# version: {}", self.version)?;
+        writeln!(out, "# options_hash: {:016x}", self.options_hash)?;
+        match self.input_hash {
+            Some(h) => writeln!(out, "# input_hash: {:016x}", h)?,
+            None => writeln!(out, "# input_hash: unknown")?,
+        }
+        writeln!(out, "# started: {}", timestamp(self.started))?;
+        writeln!(out, "# finished: {}", timestamp(self.started + self.duration))?;
+        writeln!(out, "# duration_ms: {}", self.duration.as_millis())
+    }
+}
+
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(FNV_OFFSET, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+// Contribution of the line at 0-based `index` to the input hash
+pub fn line_hash(index: usize, line: &str) -> u64 {
+    fnv1a(line.as_bytes()).wrapping_mul(FNV_PRIME.wrapping_pow(index as u32))
+}
+
+pub fn text_hash(text: &str) -> u64 {
+    text.lines()
+        .enumerate()
+        .fold(0, |hash, (index, line)| hash.wrapping_add(line_hash(index, line)))
+}
+
+// Moves the hash of a part down by the `lines` counted before it
+pub fn shift(hash: u64, lines: usize) -> u64 {
+    hash.wrapping_mul(FNV_PRIME.wrapping_pow(lines as u32))
+}
+
+// RFC 3339 in UTC, to the second
+fn timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_date(days as i64);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    )
+}
+
+// Days since 1970-01-01 to a Gregorian date (Howard Hinnant's algorithm)
+fn civil_date(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
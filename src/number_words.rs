@@ -0,0 +1,242 @@
+// `--normalize-numbers words|digits`: rewrites spelled-out numbers as digits
+// ("twenty-one" -> "21", "one hundred and five" -> "105") or whole-number
+// digit tokens as words, so mixed styles aggregate.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberStyle {
+    Words,
+    Digits,
+}
+
+impl NumberStyle {
+    pub fn parse(name: &str) -> Option<NumberStyle> {
+        match name {
+            "words" => Some(NumberStyle::Words),
+            "digits" => Some(NumberStyle::Digits),
+            _ => None,
+        }
+    }
+}
+
+const UNITS: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALES: [(&str, u64); 3] = [("billion", 1_000_000_000), ("million", 1_000_000), ("thousand", 1_000)];
+
+enum NumberWord {
+    Value(u64),
+    Hundred,
+    Scale(u64),
+}
+
+fn number_word(word: &str) -> Option<NumberWord> {
+    if let Some(i) = UNITS.iter().position(|&u| u == word) {
+        return Some(NumberWord::Value(i as u64));
+    }
+    if let Some(i) = TENS.iter().position(|&t| !t.is_empty() && t == word) {
+        return Some(NumberWord::Value(i as u64 * 10));
+    }
+    if word == "hundred" {
+        return Some(NumberWord::Hundred);
+    }
+    SCALES
+        .iter()
+        .find(|&&(s, _)| s == word)
+        .map(|&(_, v)| NumberWord::Scale(v))
+}
+
+// Splits a token into leading punctuation, the word, and trailing punctuation
+fn split_token(token: &str) -> (&str, &str, &str) {
+    let start = token.find(|c: char| c.is_alphanumeric()).unwrap_or(token.len());
+    let end = token
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_alphanumeric())
+        .map_or(start, |(i, c)| i + c.len_utf8());
+    (&token[..start], &token[start..end.max(start)], &token[end.max(start)..])
+}
+
+// Whitespace runs and the tokens between them, separators kept as-is
+fn tokens_with_separators(text: &str) -> Vec<(&str, &str)> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let start = rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len());
+        let end = rest[start..].find(char::is_whitespace).map_or(rest.len(), |i| start + i);
+        pieces.push((&rest[..start], &rest[start..end]));
+        rest = &rest[end..];
+    }
+    pieces
+}
+
+pub fn normalize(text: &str, style: NumberStyle) -> String {
+    let pieces = tokens_with_separators(text);
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < pieces.len() {
+        let (separator, token) = pieces[i];
+        out.push_str(separator);
+        match style {
+            NumberStyle::Digits => match parse_words(&pieces[i..]) {
+                Some((value, used)) => {
+                    let (lead, _, _) = split_token(pieces[i].1);
+                    let (_, _, trail) = split_token(pieces[i + used - 1].1);
+                    out.push_str(&format!("{}{}{}", lead, value, trail));
+                    i += used;
+                    continue;
+                }
+                None => out.push_str(token),
+            },
+            NumberStyle::Words => {
+                let (lead, core, trail) = split_token(token);
+                match core.parse::<u64>() {
+                    Ok(value) if core.chars().all(|c| c.is_ascii_digit()) && value < 1_000_000_000_000 => {
+                        out.push_str(&format!("{}{}{}", lead, to_words(value), trail))
+                    }
+                    _ => out.push_str(token),
+                }
+            }
+        }
+        i += 1;
+    }
+    out
+}
+
+// What the last number word was, deciding which words may follow it
+#[derive(Clone, Copy)]
+enum Last {
+    Nothing,
+    Unit,
+    Tens,
+    Hundred,
+    Scale,
+}
+
+// The longest run of number words starting at the first token, as its value
+// and the number of tokens used. A token with trailing punctuation ends the
+// run. Words combine only as numbers are spoken: a tens word with a unit
+// ("twenty-one"), a value with a following hundred or scale word. Any other
+// word ends the number, so "one two" is two numbers, not three.
+fn parse_words(pieces: &[(&str, &str)]) -> Option<(u64, usize)> {
+    let (mut total, mut current, mut last) = (0u64, 0u64, Last::Nothing);
+    // Scale words only descend: "two million three thousand"
+    let mut last_scale = u64::MAX;
+    let mut best = None;
+    for (index, &(_, token)) in pieces.iter().enumerate() {
+        let (lead, core, trail) = split_token(token);
+        if index > 0 && !lead.is_empty() {
+            break;
+        }
+        let lower = core.to_lowercase();
+        // "and" only joins number words, as in "one hundred and five"
+        if lower == "and" && matches!(last, Last::Hundred | Last::Scale) && trail.is_empty() {
+            continue;
+        }
+        let mut valid = !lower.is_empty();
+        for part in lower.split('-').map(number_word) {
+            match (part, last) {
+                (Some(NumberWord::Value(v)), Last::Nothing | Last::Hundred | Last::Scale)
+                | (Some(NumberWord::Value(v @ 1..=9)), Last::Tens) => {
+                    current += v;
+                    last = if v >= 20 { Last::Tens } else { Last::Unit };
+                }
+                (Some(NumberWord::Hundred), Last::Nothing | Last::Unit | Last::Tens) if current < 100 => {
+                    current = current.max(1) * 100;
+                    last = Last::Hundred;
+                }
+                (Some(NumberWord::Scale(s)), Last::Nothing | Last::Unit | Last::Tens | Last::Hundred)
+                    if s < last_scale =>
+                {
+                    total += current.max(1) * s;
+                    current = 0;
+                    last = Last::Scale;
+                    last_scale = s;
+                }
+                _ => {
+                    valid = false;
+                    break;
+                }
+            }
+        }
+        if !valid {
+            break;
+        }
+        best = Some((total + current, index + 1));
+        if !trail.is_empty() {
+            break;
+        }
+    }
+    best
+}
+
+pub fn to_words(n: u64) -> String {
+    if n < 20 {
+        return UNITS[n as usize].to_string();
+    }
+    if n < 100 {
+        let tens = TENS[(n / 10) as usize];
+        return match n % 10 {
+            0 => tens.to_string(),
+            unit => format!("{}-{}", tens, UNITS[unit as usize]),
+        };
+    }
+    if n < 1_000 {
+        return match n % 100 {
+            0 => format!("{} hundred", UNITS[(n / 100) as usize]),
+            rest => format!("{} hundred {}", UNITS[(n / 100) as usize], to_words(rest)),
+        };
+    }
+    let &(name, scale) = SCALES.iter().find(|&&(_, s)| n >= s).unwrap_or(&SCALES[2]);
+    match n % scale {
+        0 => format!("{} {}", to_words(n / scale), name),
+        rest => format!("{} {} {}", to_words(n / scale), name, to_words(rest)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spelled_numbers_become_digits() {
+        let digits = |text| normalize(text, NumberStyle::Digits);
+        assert_eq!(digits("twenty-one cats"), "21 cats");
+        assert_eq!(digits("one hundred and five days"), "105 days");
+        assert_eq!(digits("Two thousand three hundred people"), "2300 people");
+        assert_eq!(digits("a million  stars"), "a 1000000  stars");
+        assert_eq!(digits("(seven), eight."), "(7), 8.");
+    }
+
+    #[test]
+    fn adjacent_values_are_separate_numbers() {
+        let digits = |text| normalize(text, NumberStyle::Digits);
+        assert_eq!(digits("one two three"), "1 2 3");
+        assert_eq!(digits("Five six"), "5 6");
+        assert_eq!(digits("nineteen ninety-nine"), "19 99");
+        assert_eq!(digits("twenty one and thirty"), "21 and 30");
+        assert_eq!(digits("nineteen hundred and ninety-nine"), "1999");
+        assert_eq!(digits("two million three thousand four"), "2003004");
+        assert_eq!(digits("one thousand million"), "1000 1000000");
+    }
+
+    #[test]
+    fn and_only_joins_number_words() {
+        let digits = |text| normalize(text, NumberStyle::Digits);
+        assert_eq!(digits("cats and dogs"), "cats and dogs");
+        assert_eq!(digits("three and"), "3 and");
+        assert_eq!(digits("one, two"), "1, 2");
+    }
+
+    #[test]
+    fn digits_become_words() {
+        let words = |text| normalize(text, NumberStyle::Words);
+        assert_eq!(words("21 cats, 105."), "twenty-one cats, one hundred five.");
+        assert_eq!(words("3.14 and 1st stay"), "3.14 and 1st stay");
+        assert_eq!(to_words(2_000_040), "two million forty");
+        assert_eq!(to_words(1_001_000), "one million one thousand");
+    }
+}
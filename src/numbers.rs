@@ -0,0 +1,54 @@
+// Numeric token statistics, for profiling logs and data dumps. Works from
+// the frequency map: a word counts as numeric when it parses as a number
+// once surrounding punctuation is removed.
+
+use std::collections::HashMap;
+
+use crate::redact::shown;
+use crate::report::fixed;
+use crate::{AnalysisResult, Config};
+
+const SHOWN: usize = 5;
+
+// The number as written (punctuation removed) and its value
+pub fn parse_numeric(word: &str) -> Option<(&str, f64)> {
+    let trimmed = word
+        .trim_start_matches(['(', '[', '"', '\''])
+        .trim_end_matches([',', ';', ':', '.', '!', '?', ')', ']', '"', '\'', '%']);
+    let plain = trimmed
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+'));
+    if !plain || !trimmed.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    trimmed.parse().ok().map(|value| (trimmed, value))
+}
+
+pub fn print(config: &Config, analysis: &AnalysisResult) {
+    // "12," and "12" are the same number
+    let mut grouped: HashMap<&str, (u32, f64)> = HashMap::new();
+    for (word, &count) in &analysis.freq {
+        if let Some((number, value)) = parse_numeric(word) {
+            grouped.entry(number).or_insert((0, value)).0 += count;
+        }
+    }
+    let mut numeric: Vec<(&str, u32, f64)> = grouped.into_iter().map(|(n, (c, v))| (n, c, v)).collect();
+    numeric.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    println!("\nNumbers:");
+    let count: u32 = numeric.iter().map(|&(_, c, _)| c).sum();
+    println!("  Numeric tokens: {}", count);
+    if count == 0 {
+        return;
+    }
+    let min = numeric.iter().map(|n| n.2).fold(f64::INFINITY, f64::min);
+    let max = numeric.iter().map(|n| n.2).fold(f64::NEG_INFINITY, f64::max);
+    let mean = numeric.iter().map(|&(_, c, v)| v * c as f64).sum::<f64>() / count as f64;
+    println!("  Min: {}", fixed(config, min, 2));
+    println!("  Max: {}", fixed(config, max, 2));
+    println!("  Mean: {}", fixed(config, mean, 2));
+    println!("  Most frequent:");
+    for (word, count, _) in numeric.iter().take(SHOWN) {
+        println!("    {} ({})", shown(config.redact.as_ref(), word), count);
+    }
+}
@@ -0,0 +1,233 @@
+// Roman numerals (XIV) and ordinals (1st, 22nd) as their own token class.
+// They are left out of the word stats unless `--keep-numerals` is given,
+// since chapter headings otherwise crowd the top words of most books. A
+// Roman numeral needs numeral context: a heading word before it ("Chapter
+// XIV", "Act I"), or a small one alone on its line. Elsewhere "MIX", "DC"
+// or "LI" are words and count as such.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumeralClass {
+    Roman,
+    Ordinal,
+}
+
+const SHOWN: usize = 5;
+
+// Words that introduce a numbered division
+const HEADINGS: &[&str] = &[
+    "act", "appendix", "article", "book", "canto", "chapter", "part", "scene", "section", "stave", "vol", "volume",
+];
+
+// Alone on its line, II to XX reads as a heading; "I" stays the pronoun
+const ALONE: std::ops::RangeInclusive<u32> = 2..=20;
+
+fn core(token: &str) -> &str {
+    token.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+// The class of each raw token of one line
+pub fn classes(tokens: &[&str]) -> Vec<Option<NumeralClass>> {
+    let cores: Vec<&str> = tokens.iter().map(|t| core(t)).collect();
+    let alone = cores.iter().filter(|c| !c.is_empty()).count() == 1;
+    let mut previous = "";
+    cores
+        .iter()
+        .map(|&word| {
+            let after_heading = HEADINGS.iter().any(|h| h.eq_ignore_ascii_case(previous));
+            if !word.is_empty() {
+                previous = word;
+            }
+            if is_ordinal(word) {
+                Some(NumeralClass::Ordinal)
+            } else if roman(word).is_some_and(|value| after_heading || alone && ALONE.contains(&value)) {
+                Some(NumeralClass::Roman)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// The value of a canonical uppercase numeral
+fn roman(word: &str) -> Option<u32> {
+    if word.is_empty() || !word.chars().all(|c| "MDCLXVI".contains(c)) {
+        return None;
+    }
+    let value = roman_value(word);
+    ((1..4000).contains(&value) && to_roman(value) == word).then_some(value)
+}
+
+fn roman_value(word: &str) -> u32 {
+    let digit = |c: char| match c {
+        'M' => 1000,
+        'D' => 500,
+        'C' => 100,
+        'L' => 50,
+        'X' => 10,
+        'V' => 5,
+        _ => 1,
+    };
+    let digits: Vec<u32> = word.chars().map(digit).collect();
+    digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| {
+            if digits.get(i + 1).is_some_and(|&next| next > d) {
+                -(d as i64)
+            } else {
+                d as i64
+            }
+        })
+        .sum::<i64>()
+        .max(0) as u32
+}
+
+fn to_roman(mut value: u32) -> String {
+    const TABLE: [(u32, &str); 13] = [
+        (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"), (100, "C"), (90, "XC"), (50, "L"),
+        (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+    ];
+    let mut out = String::new();
+    for &(v, s) in &TABLE {
+        while value >= v {
+            out.push_str(s);
+            value -= v;
+        }
+    }
+    out
+}
+
+// 1st, 2nd, 3rd, 4th ... with the suffix the number actually takes
+fn is_ordinal(word: &str) -> bool {
+    let digits_end = word.find(|c: char| !c.is_ascii_digit()).unwrap_or(word.len());
+    let (digits, suffix) = word.split_at(digits_end);
+    let Ok(n) = digits.parse::<u64>() else {
+        return false;
+    };
+    let expected = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    suffix.eq_ignore_ascii_case(expected)
+}
+
+#[derive(Default)]
+pub struct NumeralCounts {
+    pub roman: HashMap<String, u32>,
+    pub ordinal: HashMap<String, u32>,
+}
+
+impl NumeralCounts {
+    pub fn is_empty(&self) -> bool {
+        self.roman.is_empty() && self.ordinal.is_empty()
+    }
+}
+
+pub fn count(text: &str) -> NumeralCounts {
+    let mut counts = NumeralCounts::default();
+    counts.add(text);
+    counts
+}
+
+impl NumeralCounts {
+    // Counts the numerals of another stretch of text
+    pub fn add(&mut self, text: &str) {
+        for line in text.lines() {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            for (token, class) in tokens.iter().zip(classes(&tokens)) {
+                let table = match class {
+                    Some(NumeralClass::Roman) => &mut self.roman,
+                    Some(NumeralClass::Ordinal) => &mut self.ordinal,
+                    None => continue,
+                };
+                *table.entry(core(token).to_lowercase()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: NumeralCounts) {
+        for (mine, theirs) in [(&mut self.roman, other.roman), (&mut self.ordinal, other.ordinal)] {
+            for (word, count) in theirs {
+                *mine.entry(word).or_insert(0) += count;
+            }
+        }
+    }
+}
+
+pub fn print(counts: &NumeralCounts) {
+    println!("\nNumerals (excluded from word stats):");
+    for (label, table) in [("Roman numerals", &counts.roman), ("Ordinals", &counts.ordinal)] {
+        let mut ranked: Vec<(&String, &u32)> = table.iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let examples: Vec<String> = ranked
+            .iter()
+            .take(SHOWN)
+            .map(|(w, c)| format!("{} ({})", w, c))
+            .collect();
+        let total = table.values().sum::<u32>();
+        match examples.is_empty() {
+            true => println!("  {}: {}", label, total),
+            false => println!("  {}: {} {}", label, total, examples.join(", ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roman_numerals_round_trip() {
+        for (word, value) in [("IV", 4), ("IX", 9), ("XIV", 14), ("XL", 40), ("MCMXCIV", 1994), ("MMMCMXCIX", 3999)] {
+            assert_eq!(roman_value(word), value, "{}", word);
+            assert_eq!(to_roman(value), word);
+        }
+    }
+
+    #[test]
+    fn only_canonical_uppercase_numerals_are_roman() {
+        assert_eq!((roman("XIV"), roman("I"), roman("MM")), (Some(14), Some(1), Some(2000)));
+        for word in ["IIII", "VX", "IC", "xiv", "MMMM", "XIVa", ""] {
+            assert_eq!(roman(word), None, "{}", word);
+        }
+    }
+
+    fn roman_words(line: &str) -> Vec<&str> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let classes = classes(&tokens);
+        tokens.into_iter().zip(classes).filter(|(_, c)| *c == Some(NumeralClass::Roman)).map(|(t, _)| t).collect()
+    }
+
+    #[test]
+    fn roman_numerals_need_numeral_context() {
+        assert_eq!(roman_words("CHAPTER XIV."), ["XIV."]);
+        assert_eq!(roman_words("Act I, Scene II"), ["I,", "II"]);
+        assert_eq!(roman_words("  XII. "), ["XII."]);
+        assert!(roman_words("I").is_empty());
+        assert!(roman_words("MIX").is_empty());
+        assert!(roman_words("DC IS A CITY NOT A MIX OF LI AND DIM MID CD").is_empty());
+        assert!(roman_words("Louis XIV was king").is_empty());
+    }
+
+    #[test]
+    fn ordinals_count_anywhere() {
+        let mut counts = count("the 1st and 22nd\nCHAPTER IV\nMIX\n");
+        assert_eq!(counts.ordinal.len(), 2);
+        assert_eq!(counts.roman.drain().collect::<Vec<_>>(), [("iv".to_string(), 1)]);
+    }
+
+    #[test]
+    fn ordinals_take_the_suffix_of_their_number() {
+        for word in ["1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22ND", "101st", "111th"] {
+            assert!(is_ordinal(word), "{}", word);
+        }
+        for word in ["1th", "2st", "11st", "12nd", "13rd", "st", "1", "1stly"] {
+            assert!(!is_ordinal(word), "{}", word);
+        }
+    }
+}
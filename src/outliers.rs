@@ -0,0 +1,105 @@
+// `--outliers N`: with several inputs, the documents whose word
+// distribution diverges most from the corpus average, by Kullback-Leibler
+// divergence in bits. The average weighs every document equally, so one
+// long file does not become the norm; mis-filed or generated documents
+// tend to top the list. Empty inputs take no part.
+
+use std::collections::HashMap;
+
+use crate::report::fixed;
+use crate::{AnalysisResult, Config, FileStatus};
+
+// Each counted input with its divergence, most divergent first; empty
+// when fewer than two inputs have words
+pub fn rank(analysis: &AnalysisResult) -> Vec<(&str, f64)> {
+    let documents: Vec<(&str, &HashMap<String, u32>, f64)> = analysis
+        .files
+        .iter()
+        .filter(|f| f.status == FileStatus::Counted && f.tokens > 0)
+        .filter_map(|f| f.freq.as_ref().map(|freq| (f.path.as_str(), freq, f.tokens as f64)))
+        .collect();
+    if documents.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut average: HashMap<&str, f64> = HashMap::new();
+    for (_, freq, tokens) in &documents {
+        for (word, &count) in *freq {
+            *average.entry(word.as_str()).or_insert(0.0) += count as f64 / tokens;
+        }
+    }
+    let n = documents.len() as f64;
+    // Every word of a document is in the average, so no term is infinite
+    let mut ranked: Vec<(&str, f64)> = documents
+        .iter()
+        .map(|&(path, freq, tokens)| {
+            let divergence = freq
+                .iter()
+                .map(|(word, &count)| {
+                    let p = count as f64 / tokens;
+                    p * (p / (average[word.as_str()] / n)).log2()
+                })
+                .sum::<f64>();
+            (path, divergence.max(0.0))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+    ranked
+}
+
+pub fn print(config: &Config, analysis: &AnalysisResult, n: usize) {
+    println!("\nOutliers (divergence from the corpus average):");
+    let ranked = rank(analysis);
+    if ranked.is_empty() {
+        println!("  Needs two or more inputs with words");
+    }
+    for (rank, (path, divergence)) in ranked.into_iter().take(n).enumerate() {
+        println!("  {:>3}. {} ({} bits)", rank + 1, path, fixed(config, divergence, 3));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileStats;
+
+    fn file(path: &str, words: &[(&str, u32)]) -> FileStats {
+        let freq: HashMap<String, u32> = words.iter().map(|&(w, c)| (w.to_string(), c)).collect();
+        FileStats {
+            path: path.to_string(),
+            status: FileStatus::Counted,
+            tokens: freq.values().sum(),
+            types: freq.len(),
+            sum_length: 0,
+            hapax: 0,
+            grade: None,
+            freq: Some(freq),
+        }
+    }
+
+    #[test]
+    fn the_odd_document_ranks_first() {
+        let analysis = AnalysisResult {
+            files: vec![
+                file("a", &[("the", 4), ("cat", 2)]),
+                file("b", &[("the", 4), ("cat", 1), ("dog", 1)]),
+                file("c", &[("lorem", 3), ("ipsum", 3)]),
+            ],
+            ..AnalysisResult::default()
+        };
+        let ranked = rank(&analysis);
+        assert_eq!(ranked[0].0, "c");
+        // Shares no words with the others: log2 of the document count
+        assert!((ranked[0].1 - 3f64.log2()).abs() < 1e-9);
+        assert!(ranked[1].1 < ranked[0].1);
+    }
+
+    #[test]
+    fn identical_documents_do_not_diverge() {
+        let analysis = AnalysisResult {
+            files: vec![file("a", &[("x", 2), ("y", 1)]), file("b", &[("x", 2), ("y", 1)])],
+            ..AnalysisResult::default()
+        };
+        assert!(rank(&analysis).iter().all(|&(_, d)| d.abs() < 1e-12));
+    }
+}
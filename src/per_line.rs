@@ -0,0 +1,28 @@
+// Per-line statistics as CSV, for spotting overlong lines and dense
+// paragraphs. Works on the raw lines: word filters do not apply here.
+
+use std::io::{self, BufWriter, Write};
+
+pub fn print_csv(text: &str) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    writeln!(out, "line,words,chars,average_word_length")?;
+    for (number, line) in text.lines().enumerate() {
+        let (words, word_chars) = line
+            .split_whitespace()
+            .fold((0usize, 0usize), |(words, chars), w| (words + 1, chars + w.chars().count()));
+        let average = if words > 0 {
+            word_chars as f64 / words as f64
+        } else {
+            0.0
+        };
+        writeln!(
+            out,
+            "{},{},{},{:.2}",
+            number + 1,
+            words,
+            line.chars().count(),
+            average
+        )?;
+    }
+    out.flush()
+}
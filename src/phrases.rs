@@ -0,0 +1,119 @@
+// Exact multi-word phrase counting over the token stream, trimmed of
+// punctuation and case-folded as word counting does, so "Hello world!"
+// matches `--phrase "hello world"`. Word filters do not apply, since they
+// would break phrases apart.
+// `--top-phrases` discovers the phrases instead: n-grams within punctuation
+// boundaries that neither start nor end with a stopword.
+
+use std::collections::HashMap;
+
+use crate::redact::shown_text;
+use crate::{Config, keywords, plain_words, stopwords};
+
+pub const DEFAULT_MAX_LEN: usize = 4;
+
+pub fn count(text: &str, config: &Config) -> Vec<(String, u32)> {
+    let tokens = plain_words(text, config);
+    config
+        .phrases
+        .iter()
+        .map(|phrase| {
+            let words = plain_words(phrase, config);
+            let count = if words.is_empty() {
+                0
+            } else {
+                tokens.windows(words.len()).filter(|window| *window == words.as_slice()).count()
+            };
+            (phrase.clone(), count as u32)
+        })
+        .collect()
+}
+
+pub fn print(config: &Config, counts: &[(String, u32)]) {
+    println!("\nPhrases:");
+    for (phrase, count) in counts {
+        println!("  \"{}\": {}", shown_text(config.redact.as_ref(), phrase), count);
+    }
+}
+
+pub fn top(text: &str, count: usize, max_len: usize) -> Vec<(String, u32)> {
+    let mut segments: Vec<Vec<String>> = vec![Vec::new()];
+    for (word, breaks) in keywords::words(text) {
+        if !word.is_empty() {
+            segments.last_mut().unwrap().push(word);
+        }
+        if breaks || segments.last().is_some_and(|s| s.is_empty()) {
+            segments.push(Vec::new());
+        }
+    }
+
+    let mut counts: HashMap<Vec<&str>, u32> = HashMap::new();
+    for segment in &segments {
+        for len in 2..=max_len.max(2) {
+            for window in segment.windows(len) {
+                let trimmed = !stopwords::is_stopword(&window[0])
+                    && !stopwords::is_stopword(&window[len - 1]);
+                if trimmed {
+                    let key = window.iter().map(String::as_str).collect();
+                    *counts.entry(key).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+    let repeated: Vec<(Vec<&str>, u32)> = counts.into_iter().filter(|&(_, c)| c >= 2).collect();
+
+    // Nested phrases: a shorter phrase that only ever occurs inside a longer
+    // one gives way to it, and a longer phrase covering under half of a
+    // shorter one's occurrences is absorbed by it
+    let contains = |long: &[&str], short: &[&str]| {
+        long.len() > short.len() && long.windows(short.len()).any(|w| w == short)
+    };
+    let mut merged: Vec<(String, u32)> = repeated
+        .iter()
+        .filter(|(phrase, c)| {
+            !repeated.iter().any(|(other, oc)| {
+                contains(other, phrase) && oc == c || contains(phrase, other) && c * 2 < *oc
+            })
+        })
+        .map(|(phrase, c)| (phrase.join(" "), *c))
+        .collect();
+    merged.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    merged.truncate(count);
+    merged
+}
+
+pub fn print_top(config: &Config, counts: &[(String, u32)]) {
+    println!("\nTop phrases:");
+    for (phrase, count) in counts {
+        println!("  \"{}\": {}", shown_text(config.redact.as_ref(), phrase), count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counted(text: &str, phrases: &[&str]) -> Vec<u32> {
+        let config = Config {
+            phrases: phrases.iter().map(|p| p.to_string()).collect(),
+            ..Config::default()
+        };
+        count(text, &config).into_iter().map(|(_, c)| c).collect()
+    }
+
+    #[test]
+    fn matches_through_punctuation_and_case() {
+        let text = "hello world, \"hello world\" and HELLO WORLD! hello world";
+        assert_eq!(counted(text, &["hello world"]), [4]);
+    }
+
+    #[test]
+    fn matches_across_lines() {
+        assert_eq!(counted("the end of\nthe line", &["of the"]), [1]);
+    }
+
+    #[test]
+    fn needs_adjacent_words() {
+        assert_eq!(counted("hello big world", &["hello world", "", "big"]), [0, 0, 1]);
+    }
+}
@@ -0,0 +1,207 @@
+// Counting pipeline: a producer thread reads and tokenizes the input in
+// batches of lines, and the counting stage folds them into the analysis.
+// The two are joined by a bounded channel, so a fast source waits for the
+// counter instead of piling up tokens in memory.
+//
+// `stream` runs the same stages straight off the input files, so memory
+// stays bounded by the vocabulary rather than the file size when no option
+// needs the whole text. Several files are counted in parallel on rayon
+// (`--jobs` threads when given) and merged in the order given.
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+use std::sync::mpsc;
+use std::thread;
+
+use rayon::prelude::*;
+
+use crate::numerals::NumeralCounts;
+use crate::structure::Structure;
+use crate::{
+    AnalysisResult, CliError, Config, FileStats, Position, line_words, metadata, open, preprocess, read_error, stem,
+    surface,
+};
+
+const BATCH_LINES: usize = 1024;
+const CHANNEL_DEPTH: usize = 4;
+
+// Extent of the counted input, for placing the next file after it
+#[derive(Default)]
+struct Totals {
+    lines: usize,
+    tokens: usize,
+    saw_text: bool,
+    // Of the raw lines, for the run metadata
+    hash: u64,
+    structure: Structure,
+}
+
+// Tokens of a batch, with its numerals when streaming, or the error that
+// ended the input
+type Batch<E> = Result<(Vec<(Position, String)>, NumeralCounts), E>;
+
+// Counts text that is already in memory and preprocessed
+pub fn count<R: BufRead + Send>(source: R, config: &Config) -> io::Result<AnalysisResult> {
+    let (analysis, _) = run(source.lines(), config, false)?;
+    Ok(analysis)
+}
+
+// Counts in-memory, preprocessed inputs, as (path, text), one after another
+// the way `stream` counts files, so n-grams and paragraphs never span two
+// of them
+pub fn count_documents<'t>(documents: impl Iterator<Item = (&'t str, &'t str)>, config: &Config) -> AnalysisResult {
+    let mut analysis = AnalysisResult::with_hasher(config.hasher);
+    let mut offset = Position::default();
+    for (path, text) in documents {
+        // In-memory bytes cannot fail to read, and came from a valid &str
+        let (part, totals) = run(text.as_bytes().lines(), config, false).expect("reading an in-memory text");
+        analysis.files.push(file_stats(path, &part, &totals, config));
+        analysis.merge(part, offset);
+        offset.line += totals.lines;
+        offset.token += totals.tokens;
+    }
+    analysis
+}
+
+// Reads, preprocesses and counts the inputs line by line
+pub fn stream(paths: &[String], config: &Config) -> Result<AnalysisResult, CliError> {
+    if let [path] = paths {
+        let (mut analysis, totals) = stream_file(path, config)?;
+        analysis.files.push(file_stats(path, &analysis, &totals, config));
+        return Ok(analysis);
+    }
+    let count_all = || {
+        paths
+            .par_iter()
+            .map(|path| stream_file(path, config))
+            .collect::<Vec<_>>()
+    };
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(config.jobs.unwrap_or(0)).build();
+    let parts = match pool {
+        Ok(pool) => pool.install(count_all),
+        Err(_) => count_all(),
+    };
+
+    let mut analysis = AnalysisResult::with_hasher(config.hasher);
+    let mut offset = Position::default();
+    // In order, so the first failing file is the one reported
+    for (path, part) in paths.iter().zip(parts) {
+        let (part, totals) = part?;
+        analysis.files.push(file_stats(path, &part, &totals, config));
+        analysis.merge(part, offset);
+        offset.line += totals.lines;
+        offset.token += totals.tokens;
+    }
+    Ok(analysis)
+}
+
+fn file_stats(path: &str, part: &AnalysisResult, totals: &Totals, config: &Config) -> FileStats {
+    FileStats::new(path, !totals.saw_text, part, config)
+}
+
+fn stream_file(path: &str, config: &Config) -> Result<(AnalysisResult, Totals), CliError> {
+    let lines = open(path)?.lines().map(|l| l.map_err(|e| read_error(path, e)));
+    run(lines, config, true)
+}
+
+fn run<E: Send>(
+    lines: impl Iterator<Item = Result<String, E>> + Send,
+    config: &Config,
+    streaming: bool,
+) -> Result<(AnalysisResult, Totals), E> {
+    let (sender, receiver) = mpsc::sync_channel::<Batch<E>>(CHANNEL_DEPTH);
+    thread::scope(|scope| {
+        let producer = scope.spawn(move || produce(lines, config, streaming, sender));
+        let mut analysis = AnalysisResult::with_hasher(config.hasher);
+        let mut numerals = NumeralCounts::default();
+        let n = config.ngrams.unwrap_or(1).max(1);
+        let mut window: VecDeque<(Position, String)> = VecDeque::with_capacity(n);
+        let mut variants = stem::Variants::new();
+        for batch in receiver {
+            let (tokens, found) = batch?;
+            for (position, word) in tokens {
+                // Stemmed here rather than by the producer, to see the surface form
+                let word = if config.stem {
+                    let stemmed = stem::stem(&word, config.language);
+                    *variants.entry(stemmed.clone()).or_default().entry(word).or_insert(0) += 1;
+                    stemmed
+                } else {
+                    word
+                };
+                if n == 1 {
+                    analysis.add(position, word);
+                    continue;
+                }
+                // `--ngrams`: each run of n counted tokens, placed at its first
+                if window.len() == n {
+                    window.pop_front();
+                }
+                window.push_back((position, word));
+                if window.len() == n {
+                    let gram: Vec<&str> = window.iter().map(|(_, w)| w.as_str()).collect();
+                    analysis.add(window[0].0, gram.join(if config.chars { "" } else { " " }));
+                }
+            }
+            numerals.merge(found);
+        }
+        if streaming && !config.keep_numerals {
+            analysis.numerals = Some(numerals);
+        }
+        let totals: Totals = producer.join().unwrap_or_default();
+        if streaming {
+            analysis.input_hash = Some(totals.hash);
+        }
+        analysis.structure = Some(totals.structure);
+        if config.stem {
+            analysis.variants = Some(variants);
+        }
+        Ok((analysis, totals))
+    })
+}
+
+fn produce<E>(
+    lines: impl Iterator<Item = Result<String, E>>,
+    config: &Config,
+    streaming: bool,
+    sender: mpsc::SyncSender<Batch<E>>,
+) -> Totals {
+    let mut totals = Totals { structure: Structure::new(), ..Totals::default() };
+    let mut tokens = Vec::new();
+    let mut numerals = NumeralCounts::default();
+    for (line, content) in lines.enumerate() {
+        let content = match content {
+            Ok(content) => content,
+            Err(e) => {
+                let _ = sender.send(Err(e));
+                return totals;
+            }
+        };
+        if streaming {
+            totals.hash = totals.hash.wrapping_add(metadata::line_hash(line, &content));
+        }
+        let content = if streaming { preprocess::apply(&content, config) } else { content.into() };
+        totals.lines += 1;
+        totals.saw_text |= !content.trim().is_empty();
+        if streaming && !config.keep_numerals {
+            numerals.add(&content);
+        }
+        let mut counted = 0;
+        for (w, numeral) in line_words(&content, config) {
+            totals.tokens += 1;
+            if let Some(word) = surface(w, numeral, config) {
+                tokens.push((Position { token: totals.tokens, line: line + 1 }, word));
+                counted += 1;
+            }
+        }
+        totals.structure.add_line(&content, counted);
+        // A closed channel means the counter stopped; nothing left to do
+        if (line + 1) % BATCH_LINES == 0 {
+            let batch = (std::mem::take(&mut tokens), std::mem::take(&mut numerals));
+            if sender.send(Ok(batch)).is_err() {
+                return totals;
+            }
+        }
+    }
+    let _ = sender.send(Ok((tokens, numerals)));
+    totals
+}
@@ -0,0 +1,109 @@
+// Text clean-up passes that run before tokenization, each behind its own
+// option.
+
+use std::borrow::Cow;
+
+use crate::{Config, contractions, number_words, recase};
+
+pub fn apply<'a>(text: &'a str, config: &Config) -> Cow<'a, str> {
+    let mut text = Cow::Borrowed(text);
+    if config.ocr_cleanup {
+        text = Cow::Owned(ocr_cleanup(&text));
+    }
+    // OCR'd books are full of line-break hyphenation too
+    if config.rejoin_hyphenation || config.ocr_cleanup {
+        text = Cow::Owned(rejoin_hyphenation(&text));
+    }
+    if config.recase {
+        text = Cow::Owned(recase::recase(&text).into_owned());
+    }
+    if !config.keep_unicode_punct && text.contains(is_unicode_punct) {
+        text = Cow::Owned(normalize_punctuation(&text));
+    }
+    // After normalization, so "don’t" expands too
+    if config.expand_contractions {
+        text = Cow::Owned(contractions::expand(&text));
+    }
+    if let Some(style) = config.normalize_numbers {
+        text = Cow::Owned(number_words::normalize(&text, style));
+    }
+    text
+}
+
+fn is_unicode_punct(c: char) -> bool {
+    matches!(
+        c,
+        '‘' | '’' | '‚' | '‛' | '′' | '“' | '”' | '„' | '‟' | '″' | '‐' | '‑' | '‒' | '–' | '—' | '―' | '…'
+    )
+}
+
+// Curly quotes, dashes and the ellipsis character to their ASCII forms, so
+// "don’t" and "don't" are the same word
+fn normalize_punctuation(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '‘' | '’' | '‚' | '‛' | '′' => out.push('\''),
+            '“' | '”' | '„' | '‟' | '″' => out.push('"'),
+            '‐' | '‑' | '‒' | '–' | '—' | '―' => out.push('-'),
+            '…' => out.push_str("..."),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// "analy-\nsis" -> "analysis": a line ending in a letter and a hyphen,
+// followed by a line starting (after indentation) with a lowercase letter.
+// The rest of the next line stays put, so line numbers do not shift.
+fn rejoin_hyphenation(text: &str) -> String {
+    let mut lines: Vec<String> = text.split('\n').map(String::from).collect();
+    for i in 1..lines.len() {
+        let line = lines[i - 1].trim_end_matches('\r');
+        let Some(stem) = line.strip_suffix('-') else {
+            continue;
+        };
+        let next = lines[i].trim_start_matches([' ', '\t']);
+        if !stem.ends_with(char::is_alphabetic) || !next.starts_with(char::is_lowercase) {
+            continue;
+        }
+        let (word, remainder) = next.split_at(next.find(char::is_whitespace).unwrap_or(next.len()));
+        let mut joined = format!("{}{}", stem, word);
+        if lines[i - 1].ends_with('\r') {
+            joined.push('\r');
+        }
+        let remainder = remainder.trim_start_matches([' ', '\t']).to_string();
+        lines[i - 1] = joined;
+        lines[i] = remainder;
+    }
+    lines.join("\n")
+}
+
+// Common OCR artifacts: ligature glyphs, soft hyphens and zero-width
+// characters inside words, and punctuation stutters like ",," or "!!!"
+// (a three-dot ellipsis and dash runs standing in for em dashes are left alone)
+fn ocr_cleanup(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{fb00}' => out.push_str("ff"),
+            '\u{fb01}' => out.push_str("fi"),
+            '\u{fb02}' => out.push_str("fl"),
+            '\u{fb03}' => out.push_str("ffi"),
+            '\u{fb04}' => out.push_str("ffl"),
+            '\u{fb05}' | '\u{fb06}' => out.push_str("st"),
+            '\u{00ad}' | '\u{200b}' | '\u{200c}' | '\u{200d}' | '\u{feff}' => {}
+            c if c.is_ascii_punctuation() && c != '-' && chars.peek() == Some(&c) => {
+                let mut run = 1;
+                while chars.next_if_eq(&c).is_some() {
+                    run += 1;
+                }
+                let keep = if c == '.' && run == 3 { 3 } else { 1 };
+                out.extend(std::iter::repeat_n(c, keep));
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
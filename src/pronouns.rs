@@ -0,0 +1,53 @@
+// `--pronouns`: first-, second- and third-person pronoun densities per 1000
+// words. Contractions count by their pronoun ("we've" is first person) and
+// an all-caps "US" is read as the country, not the pronoun.
+
+use crate::Config;
+use crate::report::fixed;
+
+const FIRST: &[&str] = &["i", "me", "mine", "my", "myself", "our", "ours", "ourselves", "us", "we"];
+const SECOND: &[&str] = &["thee", "thou", "thy", "you", "your", "yours", "yourself", "yourselves"];
+const THIRD: &[&str] = &[
+    "he", "her", "hers", "herself", "him", "himself", "his", "it", "its", "itself", "she",
+    "their", "theirs", "them", "themselves", "they",
+];
+
+#[derive(Default)]
+pub struct PronounProfile {
+    pub words: usize,
+    pub first: usize,
+    pub second: usize,
+    pub third: usize,
+}
+
+pub fn profile(text: &str) -> PronounProfile {
+    let mut p = PronounProfile::default();
+    for raw in text.split_whitespace() {
+        let word = raw.trim_matches(|c: char| !c.is_alphanumeric());
+        if word.is_empty() {
+            continue;
+        }
+        p.words += 1;
+        if word == "US" {
+            continue;
+        }
+        let word = word.split(['\'', '’']).next().unwrap_or(word).to_lowercase();
+        let word = word.as_str();
+        if FIRST.contains(&word) {
+            p.first += 1;
+        } else if SECOND.contains(&word) {
+            p.second += 1;
+        } else if THIRD.contains(&word) {
+            p.third += 1;
+        }
+    }
+    p
+}
+
+pub fn print(config: &Config, p: &PronounProfile) {
+    let per_thousand = |n: usize| if p.words == 0 { 0.0 } else { n as f64 * 1000.0 / p.words as f64 };
+    println!("\nPronouns (per 1000 words):");
+    println!("  First person:  {} ({})", fixed(config, per_thousand(p.first), 1), p.first);
+    println!("  Second person: {} ({})", fixed(config, per_thousand(p.second), 1), p.second);
+    println!("  Third person:  {} ({})", fixed(config, per_thousand(p.third), 1), p.third);
+}
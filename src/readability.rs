@@ -0,0 +1,148 @@
+// Readability: Flesch Reading Ease, the Flesch-Kincaid grade level and the
+// Gunning Fog index from per-language syllable estimates, plus target
+// audience bands for `--audience`.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::Config;
+use crate::csv::csv_field;
+use crate::report::fixed;
+use crate::language::{self, Language};
+use crate::sentence;
+
+#[derive(Default, Clone, Copy)]
+pub struct Readability {
+    pub language: Option<Language>,
+    pub sentences: usize,
+    pub words: usize,
+    pub syllables: usize,
+    // Words of three or more syllables, for the Fog index
+    pub complex_words: usize,
+}
+
+impl Readability {
+    pub fn grade(&self) -> f64 {
+        if self.words == 0 || self.sentences == 0 {
+            return 0.0;
+        }
+        0.39 * (self.words as f64 / self.sentences as f64)
+            + 11.8 * (self.syllables as f64 / self.words as f64)
+            - 15.59
+    }
+
+    // 0-100, higher is easier
+    pub fn ease(&self) -> f64 {
+        if self.words == 0 || self.sentences == 0 {
+            return 0.0;
+        }
+        206.835
+            - 1.015 * (self.words as f64 / self.sentences as f64)
+            - 84.6 * (self.syllables as f64 / self.words as f64)
+    }
+
+    // Years of schooling needed on a first reading
+    pub fn fog(&self) -> f64 {
+        if self.words == 0 || self.sentences == 0 {
+            return 0.0;
+        }
+        0.4 * (self.words as f64 / self.sentences as f64
+            + 100.0 * self.complex_words as f64 / self.words as f64)
+    }
+}
+
+// The language given with --language, or the detected one
+pub fn language(text: &str, config: &Config) -> Language {
+    config.language.unwrap_or_else(|| language::detect(text))
+}
+
+// Readability works on the raw text: word filters would distort it
+pub fn measure(text: &str, config: &Config) -> Readability {
+    let language = language(text, config);
+    sentence::split(text, &config.sentence_rules)
+        .iter()
+        .map(|s| measure_sentence(s, language))
+        .fold(Readability::default(), |total, r| Readability {
+            language: Some(language),
+            sentences: total.sentences + r.sentences,
+            words: total.words + r.words,
+            syllables: total.syllables + r.syllables,
+            complex_words: total.complex_words + r.complex_words,
+        })
+}
+
+fn measure_sentence(sentence: &str, language: Language) -> Readability {
+    sentence
+        .split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphabetic))
+        .fold(Readability { sentences: 1, ..Readability::default() }, |mut r, word| {
+            let syllables = language.syllables(word);
+            r.words += 1;
+            r.syllables += syllables;
+            r.complex_words += usize::from(syllables >= 3);
+            r
+        })
+}
+
+// One CSV row per sentence so editors can sort by difficulty
+pub fn export_sentences(path: &str, text: &str, config: &Config) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "sentence,words,syllables,grade,text")?;
+    let language = language(text, config);
+    for (number, s) in sentence::split(text, &config.sentence_rules).iter().enumerate() {
+        let r = measure_sentence(s, language);
+        let flat = match &config.redact {
+            Some(redactor) => redactor.text(s),
+            None => s.split_whitespace().collect::<Vec<_>>().join(" "),
+        };
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            number + 1,
+            r.words,
+            r.syllables,
+            fixed(config, r.grade(), 1),
+            csv_field(&flat)
+        )?;
+    }
+    out.flush()
+}
+
+// Target reader for `--audience`, as the highest acceptable grade level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Audience {
+    Grade8,
+    College,
+    Expert,
+}
+
+impl Audience {
+    pub fn parse(name: &str) -> Option<Audience> {
+        match name {
+            "grade8" => Some(Audience::Grade8),
+            "college" => Some(Audience::College),
+            "expert" => Some(Audience::Expert),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Audience::Grade8 => "grade8",
+            Audience::College => "college",
+            Audience::Expert => "expert",
+        }
+    }
+
+    pub fn max_grade(self) -> f64 {
+        match self {
+            Audience::Grade8 => 8.0,
+            Audience::College => 16.0,
+            Audience::Expert => 20.0,
+        }
+    }
+
+    pub fn accepts(self, grade: f64) -> bool {
+        grade <= self.max_grade()
+    }
+}
@@ -0,0 +1,47 @@
+// `--jobs N` for runs that need every input whole: reads (and decompresses)
+// the files on N threads and returns all the texts, so reading is done
+// before counting starts and memory holds the whole corpus, as those
+// options need it anyway. Counting-only runs stream instead
+// (`pipeline::stream`). Each worker takes the next unread file from a
+// shared counter; texts come back in the order given no matter which
+// finishes first, and the first failing file in that order is reported.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::{CliError, read_text};
+
+pub fn read_parallel(paths: &[String], jobs: usize) -> Result<Vec<String>, CliError> {
+    let jobs = jobs.clamp(1, paths.len().max(1));
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::sync_channel(jobs);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(path) = paths.get(index) else { break };
+                    // The receiver hangs up after the first error
+                    if sender.send((index, read_text(path))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending: Vec<Option<Result<String, CliError>>> = paths.iter().map(|_| None).collect();
+        let mut texts = Vec::with_capacity(paths.len());
+        for (index, result) in receiver.iter() {
+            pending[index] = Some(result);
+            while let Some(result) = pending.get_mut(texts.len()).and_then(Option::take) {
+                texts.push(result?);
+            }
+        }
+        Ok(texts)
+    })
+}
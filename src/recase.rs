@@ -0,0 +1,60 @@
+// Case repair for texts typed in one case, like old ALL-CAPS transcripts or
+// all-lowercase chat logs: everything is lowercased, then sentence starts
+// and the pronoun "I" are capitalized again. Sentence splitting and name
+// detection key off capitals, so they work on the repaired text.
+// `--recase` runs it before tokenizing; texts in mixed case pass through.
+
+use std::borrow::Cow;
+
+// Share of uppercase letters above which a text counts as ALL-CAPS
+const CAPS_SHARE: f64 = 0.9;
+
+// True when the text has no lowercase letters to speak of, or no capitals
+pub fn is_single_case(text: &str) -> bool {
+    let (mut upper, mut lower) = (0usize, 0usize);
+    for c in text.chars() {
+        if c.is_uppercase() {
+            upper += 1;
+        } else if c.is_lowercase() {
+            lower += 1;
+        }
+    }
+    let letters = upper + lower;
+    letters > 0 && (upper == 0 || upper as f64 / letters as f64 >= CAPS_SHARE)
+}
+
+pub fn recase(text: &str) -> Cow<'_, str> {
+    if !is_single_case(text) {
+        return Cow::Borrowed(text);
+    }
+    let lower: Vec<char> = text.to_lowercase().chars().collect();
+    let mut out = String::with_capacity(text.len());
+    // A sentence starts the text and follows `.`, `!` or `?` and whitespace
+    let mut sentence_start = true;
+    let mut after_terminator = false;
+    for (i, &c) in lower.iter().enumerate() {
+        if c.is_alphabetic() {
+            let before = i.checked_sub(1).map(|j| lower[j]);
+            let after = lower.get(i + 1).copied();
+            let alone = |n: Option<char>| n.is_none_or(|n| !n.is_alphanumeric());
+            if sentence_start || (c == 'i' && alone(before) && (alone(after) || after == Some('\''))) {
+                out.extend(c.to_uppercase());
+            } else {
+                out.push(c);
+            }
+            sentence_start = false;
+            after_terminator = false;
+            continue;
+        }
+        out.push(c);
+        if matches!(c, '.' | '!' | '?') {
+            after_terminator = true;
+        } else if c.is_whitespace() && after_terminator {
+            sentence_start = true;
+        } else if c.is_numeric() {
+            sentence_start = false;
+            after_terminator = false;
+        }
+    }
+    Cow::Owned(out)
+}
@@ -0,0 +1,183 @@
+// `--redact emails,numbers,names`: masks sensitive token classes wherever
+// words are shown (frequency listings, sentence exports, model exports).
+// A masked token becomes its class and a stable hash, e.g. "[email:1c9d44f2]",
+// so repeated tokens still line up without revealing the value.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::sentence::SentenceRules;
+use crate::{metadata, numbers, stopwords};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenClass {
+    Email,
+    Number,
+    Name,
+}
+
+#[derive(Default)]
+pub struct Redactor {
+    classes: Vec<TokenClass>,
+    // Lowercased words seen capitalized mid-sentence in the source
+    names: HashSet<String>,
+}
+
+impl Redactor {
+    pub fn parse(list: &str) -> Result<Redactor, String> {
+        let mut redactor = Redactor::default();
+        for name in list.split(',').map(str::trim) {
+            let class = match name {
+                "emails" => TokenClass::Email,
+                "numbers" => TokenClass::Number,
+                "names" => TokenClass::Name,
+                _ => return Err(format!("Unknown class '{}': expected emails, numbers or names", name)),
+            };
+            redactor.classes.push(class);
+        }
+        Ok(redactor)
+    }
+
+    pub fn needs_source(&self) -> bool {
+        self.classes.contains(&TokenClass::Name)
+    }
+
+    // Name detection: capitalized words that do not start a sentence. A
+    // period after an abbreviation ("Dr. Smith") does not end one.
+    pub fn learn_names(&mut self, text: &str, rules: &SentenceRules) {
+        if !self.needs_source() {
+            return;
+        }
+        let mut sentence_start = true;
+        for token in text.split_whitespace() {
+            let word = token.trim_matches(|c: char| !c.is_alphanumeric());
+            let mut chars = word.chars();
+            let capitalized = chars.next().is_some_and(char::is_uppercase)
+                && chars.clone().next().is_some()
+                && chars.all(|c| c.is_lowercase() || c == '\'');
+            let lower = word.to_lowercase();
+            if capitalized && !sentence_start && !stopwords::is_stopword(&lower) {
+                self.names.insert(lower);
+            }
+            let end = token.trim_end_matches(['"', '\'', ')', ']', '”', '’']);
+            sentence_start = end.ends_with(['!', '?'])
+                || end.strip_suffix('.').is_some_and(|word| !rules.is_abbreviation(word));
+        }
+    }
+
+    fn classify(&self, word: &str) -> Option<TokenClass> {
+        let core = word.trim_matches(|c: char| !c.is_alphanumeric());
+        self.classes.iter().copied().find(|class| match class {
+            TokenClass::Email => core.split_once('@').is_some_and(|(user, host)| {
+                !user.is_empty() && host.contains('.')
+            }),
+            TokenClass::Number => {
+                numbers::parse_numeric(word).is_some()
+                    || core.chars().filter(char::is_ascii_digit).count() >= 3
+            }
+            TokenClass::Name => self.names.contains(&core.to_lowercase()),
+        })
+    }
+
+    pub fn word<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        match self.classify(word) {
+            Some(class) => {
+                let label = match class {
+                    TokenClass::Email => "email",
+                    TokenClass::Number => "number",
+                    TokenClass::Name => "name",
+                };
+                // Hash the bare token so "x@y.org" and "x@y.org," match
+                Cow::Owned(mask(label, word))
+            }
+            None => Cow::Borrowed(word),
+        }
+    }
+
+    // A transcript speaker label: under `names` always a name, since
+    // labels are taken out of the text names are learned from
+    pub fn speaker<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match self.classes.contains(&TokenClass::Name) {
+            true => Cow::Owned(mask("name", name)),
+            false => self.word(name),
+        }
+    }
+
+    // Redacts every whitespace-separated token of a passage
+    pub fn text(&self, text: &str) -> String {
+        text.split_whitespace()
+            .map(|w| self.word(w))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn mask(label: &str, value: &str) -> String {
+    let core = value.trim_matches(|c: char| !c.is_alphanumeric());
+    let hash = metadata::fnv1a(core.to_lowercase().as_bytes()) as u32;
+    format!("[{}:{:08x}]", label, hash)
+}
+
+// Shown words, redacted when `--redact` is active
+pub fn shown<'a>(redactor: Option<&Redactor>, word: &'a str) -> Cow<'a, str> {
+    redactor.map_or(Cow::Borrowed(word), |r| r.word(word))
+}
+
+// Shown passages, each word redacted when `--redact` is active
+pub fn shown_text<'a>(redactor: Option<&Redactor>, text: &'a str) -> Cow<'a, str> {
+    redactor.map_or(Cow::Borrowed(text), |r| Cow::Owned(r.text(text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, tokens};
+
+    fn redacted(text: &str, classes: &str) -> Vec<String> {
+        let config = Config {
+            redact: Some(Redactor::parse(classes).unwrap()),
+            ..Config::default()
+        };
+        tokens(text, &config)
+            .map(|(_, w)| shown(config.redact.as_ref(), &w).into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn masks_emails_under_the_default_tokenizer() {
+        let words = redacted("Write to john@example.com today.", "emails");
+        assert_eq!(words.len(), 4);
+        assert!(words[2].starts_with("[email:"), "{:?}", words);
+        assert_eq!(words[3], "today");
+    }
+
+    #[test]
+    fn same_email_gets_same_mask() {
+        let words = redacted("x@y.org and X@Y.org,", "emails");
+        assert_eq!(words[0], words[2]);
+    }
+
+    #[test]
+    fn names_after_a_title_are_learned() {
+        let mut redactor = Redactor::parse("names").unwrap();
+        redactor.learn_names("We met Dr. Smith there. Then Mrs. Jones left.", &SentenceRules::default());
+        assert!(redactor.word("Smith").starts_with("[name:"));
+        assert!(redactor.word("Jones.").starts_with("[name:"));
+        assert_eq!(redactor.word("Then"), "Then");
+    }
+
+    #[test]
+    fn speakers_are_names_under_names_only() {
+        let names = Redactor::parse("names").unwrap();
+        assert_eq!(names.speaker("ALICE"), names.speaker("Alice"));
+        assert!(names.speaker("Alice").starts_with("[name:"));
+        assert_eq!(Redactor::parse("emails").unwrap().speaker("Alice"), "Alice");
+    }
+
+    #[test]
+    fn masks_numbers() {
+        let words = redacted("call 5551234 now", "numbers");
+        assert!(words[1].starts_with("[number:"));
+        assert_eq!(words[0], "call");
+    }
+}
@@ -0,0 +1,685 @@
+// Text report. Each statistic is computed only when it is printed, so a
+// `--stat` selection skips the work for everything it leaves out.
+
+use std::borrow::Cow;
+use std::io::{self, BufWriter, Write};
+
+use crate::metadata::RunMetadata;
+use crate::readability::Readability;
+use crate::sentence;
+use crate::redact::shown;
+use crate::tokenizer::Tokenizer;
+use crate::{AnalysisResult, Config, FileStatus, STDIN_PATH, word_length, bands, brevity, chars, dates, diversity, encoding, estimate, extensions, freq, glossary, json, keywords, chart, language, lengths, numbers, numerals, outliers, phrases, pronouns, sentence_types, sentiment, stem, stopwords, temporal, trace, transcript, weights, whitespace};
+
+// Report layout selected with `--format`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    // Files as columns, headline stats as rows
+    Table,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "table" => Some(Format::Table),
+            _ => None,
+        }
+    }
+}
+
+// Order of the printed word lists, `--sort`; `--reverse` flips it
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortOrder {
+    // Most frequent first
+    #[default]
+    Count,
+    // A to Z
+    Alpha,
+    // Longest first
+    Length,
+}
+
+impl SortOrder {
+    pub fn parse(name: &str) -> Option<SortOrder> {
+        match name {
+            "count" => Some(SortOrder::Count),
+            "alpha" => Some(SortOrder::Alpha),
+            "length" => Some(SortOrder::Length),
+            _ => None,
+        }
+    }
+}
+
+// `words` (ranked by count) in the configured order; ties fall back to
+// count, then alphabetical
+fn ordered<'a>(config: &Config, mut words: Vec<(&'a String, u32)>) -> Vec<(&'a String, u32)> {
+    match config.sort {
+        SortOrder::Count => {}
+        SortOrder::Alpha => words.sort_by(|a, b| a.0.cmp(b.0)),
+        SortOrder::Length => words.sort_by_key(|w| std::cmp::Reverse(word_length(w.0))),
+    }
+    if config.reverse {
+        words.reverse();
+    }
+    words
+}
+
+// A statistic selectable with `--stat`, printed in the order given
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stat {
+    Total,
+    Unique,
+    Average,
+    MostCommon,
+    Entropy,
+    Brevity,
+    Lines,
+    Paragraphs,
+    Diversity,
+    Hapax,
+    Top(usize),
+}
+
+const DEFAULT_STATS: &[Stat] = &[
+    Stat::Total,
+    Stat::Unique,
+    Stat::Average,
+    Stat::MostCommon,
+    Stat::Hapax,
+    Stat::Lines,
+    Stat::Paragraphs,
+];
+
+// `--stat` picks, or the defaults; `--top N` adds the ranked list
+fn selected_stats(config: &Config) -> Vec<Stat> {
+    let mut stats = if config.stats.is_empty() {
+        DEFAULT_STATS.to_vec()
+    } else {
+        config.stats.clone()
+    };
+    if let Some(n) = config.top
+        && !stats.iter().any(|s| matches!(s, Stat::Top(_)))
+    {
+        stats.push(Stat::Top(n));
+    }
+    stats
+}
+
+impl Stat {
+    pub fn parse(name: &str) -> Result<Stat, String> {
+        match name {
+            "total" => Ok(Stat::Total),
+            "unique" => Ok(Stat::Unique),
+            "average" => Ok(Stat::Average),
+            "most-common" => Ok(Stat::MostCommon),
+            "entropy" => Ok(Stat::Entropy),
+            "brevity" => Ok(Stat::Brevity),
+            "lines" => Ok(Stat::Lines),
+            "paragraphs" => Ok(Stat::Paragraphs),
+            "diversity" => Ok(Stat::Diversity),
+            "hapax" => Ok(Stat::Hapax),
+            _ => match name.strip_prefix("top:") {
+                Some(n) => n
+                    .parse()
+                    .map(Stat::Top)
+                    .map_err(|_| "Top count is not a number".to_string()),
+                None => Err(
+                    "Expected total, unique, average, most-common, entropy, brevity, lines, paragraphs, diversity, hapax or top:N".to_string(),
+                ),
+            },
+        }
+    }
+}
+
+pub fn print_report(config: &Config, analysis: &AnalysisResult) {
+    println!("=== Text Analyzer Results ===");
+    if config.files.len() > 1 {
+        println!("Files: {}", config.file_path);
+    } else {
+        println!("File: {}", config.file_path);
+    }
+    let empty = empty_files(analysis);
+    if !empty.is_empty() {
+        println!("Empty (counted as zero): {}", empty.join(", "));
+    }
+    let duplicates = duplicate_files(analysis);
+    if !duplicates.is_empty() {
+        println!("Duplicates (not counted):");
+        for (path, of, similarity) in duplicates {
+            println!("  {}: {} similar to {}", path, fixed(config, similarity, 3), of);
+        }
+    }
+    println!("Filters Applied:");
+    println!("  Minimum length: {}", config.min_length);
+    if config.min_count > 0 {
+        println!("  Minimum count: {}", config.min_count);
+    }
+    if let Some(c) = config.starts_with {
+        println!("  Starts with: {}", c);
+    }
+    if let Some(suffix) = &config.ends_with {
+        println!("  Ends with: {}", suffix);
+    }
+    if let Some(part) = &config.contains {
+        println!("  Contains: {}", part);
+    }
+    if config.chars {
+        println!("  Counting: characters");
+    }
+    if config.keep_punct {
+        println!("  Punctuation: kept");
+    }
+    if config.case_sensitive {
+        println!("  Case-sensitive: yes");
+    }
+    if config.stem {
+        let language = config.language.map_or("english", |l| l.name());
+        println!("  Stemmed: {}", language);
+    }
+    if let Some(n) = config.ngrams.filter(|&n| n > 1) {
+        println!("  N-grams: {}", n);
+    }
+    match &config.stopwords {
+        Some(stopwords::StopList::English) => println!("  Stopwords: English"),
+        Some(stopwords::StopList::Custom(words)) => println!("  Stopwords: {} custom", words.len()),
+        None => {}
+    }
+    if !config.exclude.is_empty() {
+        println!("  Excluded: {} words", config.exclude.len());
+    }
+    if let Some(pattern) = &config.token_pattern {
+        println!("  Token pattern: {}", pattern.as_str());
+    } else if config.tokenizer == Tokenizer::Whitespace {
+        println!("  Tokenizer: whitespace");
+    }
+    if let Some(pattern) = &config.match_pattern {
+        println!("  Match: {}", pattern.as_str());
+    }
+    if let Some(pattern) = &config.exclude_match {
+        println!("  Exclude match: {}", pattern.as_str());
+    }
+
+    println!("\nStats:");
+    for stat in selected_stats(config) {
+        print_stat(stat, config, analysis);
+    }
+
+    if let Some(counts) = analysis.numerals.as_ref().filter(|c| !c.is_empty()) {
+        numerals::print(counts);
+    }
+    if let Some(p) = &analysis.pronouns {
+        pronouns::print(config, p);
+    }
+    if let Some(o) = &analysis.temporal {
+        temporal::print(config, o);
+    }
+    if let Some(mix) = &analysis.language_mix {
+        language::print_mix(config, mix);
+    }
+    if let Some(stats) = &analysis.sentence_stats {
+        sentence::print_stats(config, stats);
+    }
+    if let Some(types) = &analysis.sentence_types {
+        sentence_types::print(config, types);
+    }
+    if let Some(entries) = &analysis.glossary {
+        glossary::print(config, entries);
+    }
+    if let Some(k) = &analysis.keywords {
+        keywords::print(config, k);
+    }
+    if !analysis.summary.is_empty() {
+        keywords::print_summary(config, &analysis.summary);
+    }
+    if !analysis.phrases.is_empty() {
+        phrases::print(config, &analysis.phrases);
+    }
+    if !analysis.top_phrases.is_empty() {
+        phrases::print_top(config, &analysis.top_phrases);
+    }
+    if let Some(reference) = &config.bands {
+        bands::print(config, analysis, reference);
+    }
+    if let Some(variants) = &analysis.variants {
+        stem::print_variants(config, variants);
+    }
+    if !config.rank_of.is_empty() {
+        freq::print_rank_of(config, analysis);
+    }
+    if config.hapax {
+        freq::print_hapax(config, analysis);
+    }
+    if let Some(suggestions) = &analysis.stopword_suggestions {
+        stopwords::print_suggestions(config, suggestions);
+    }
+    if let Some(weights) = &config.weights {
+        weights::print(config, analysis, weights);
+    }
+    if config.chars {
+        chars::print_categories(config, analysis);
+    }
+    if config.char_pairs {
+        chars::print_pairs(analysis);
+    }
+    if let Some(coverage) = &analysis.alphabet {
+        chars::print_coverage(config, coverage);
+    }
+    if config.numbers {
+        numbers::print(config, analysis);
+    }
+    if config.length_dist {
+        lengths::print(config, analysis);
+    }
+    if let Some(stats) = &analysis.dates {
+        dates::print(stats);
+    }
+    if let Some(arc) = &analysis.sentiment {
+        sentiment::print(config, arc);
+    }
+    if let Some(e) = &analysis.estimate {
+        estimate::print(e);
+    }
+    if let Some(t) = &analysis.transcript {
+        transcript::print(config, t);
+    }
+    if let Some(stats) = &analysis.whitespace {
+        whitespace::print(stats);
+    }
+    if let Some(r) = &analysis.readability {
+        print_readability(config, r);
+    }
+    if let Some(n) = config.outliers {
+        outliers::print(config, analysis, n);
+    }
+    if config.by_extension && !analysis.files.is_empty() {
+        extensions::print(config, analysis);
+    }
+    if let Some(traced) = &analysis.token_trace {
+        trace::print(config, traced);
+    }
+    if config.detailed {
+        // Panics like println! does when stdout is gone
+        print_detailed(config, analysis).expect("failed printing to stdout");
+    }
+}
+
+fn print_readability(config: &Config, r: &Readability) {
+    let grade = r.grade();
+    println!("\nReadability:");
+    if let Some(language) = r.language {
+        let source = if config.language.is_some() { "given" } else { "detected" };
+        println!("  Language: {} ({})", language.name(), source);
+    }
+    println!("  Flesch Reading Ease: {}", fixed(config, r.ease(), 1));
+    println!("  Flesch-Kincaid grade: {}", fixed(config, grade, 1));
+    println!("  Gunning Fog index: {}", fixed(config, r.fog(), 1));
+    if let Some(audience) = config.audience {
+        let gap = grade - audience.max_grade();
+        if audience.accepts(grade) {
+            println!(
+                "  Audience {} (max grade {}): PASS, {} below target",
+                audience.name(),
+                fixed(config, audience.max_grade(), 1),
+                fixed(config, -gap, 1)
+            );
+        } else {
+            println!(
+                "  Audience {} (max grade {}): FAIL, {} above target",
+                audience.name(),
+                fixed(config, audience.max_grade(), 1),
+                fixed(config, gap, 1)
+            );
+        }
+    }
+}
+
+// What a counted token is called in the report
+pub(crate) fn unit(config: &Config) -> &'static str {
+    if config.chars { "character" } else { "word" }
+}
+
+fn print_stat(stat: Stat, config: &Config, analysis: &AnalysisResult) {
+    let redactor = config.redact.as_ref();
+    let unit = unit(config);
+    match stat {
+        Stat::Total => println!("  Total {} count: {}", unit, analysis.total_words()),
+        Stat::Unique => println!("  Number of unique {}s: {}", unit, analysis.freq.len()),
+        Stat::Average => println!("  Average {} length: {} chars", unit, fixed(config, average_length(analysis), 2)),
+        Stat::MostCommon => {
+            let leaders = leaders(config, analysis);
+            let label = if config.content_words_only { "content word" } else { unit };
+            match leaders.as_slice() {
+                [] => println!("  No {}s found.", unit),
+                [(word, count)] => println!(
+                    "  Most common {}: \"{}\" with count {} ({}%)",
+                    label,
+                    shown(redactor, word),
+                    count,
+                    fixed(config, percent(analysis, *count), 1)
+                ),
+                _ => {
+                    println!("  Most common {}s:", label);
+                    for (rank, (word, count)) in leaders.iter().enumerate() {
+                        println!(
+                            "    {:>3}. {} ({}, {}%)",
+                            rank + 1,
+                            shown(redactor, word),
+                            count,
+                            fixed(config, percent(analysis, *count), 1)
+                        );
+                    }
+                }
+            }
+        }
+        Stat::Entropy => println!("  Entropy: {} bits per {}", fixed(config, entropy(analysis), 3), unit),
+        Stat::Brevity => match brevity::correlation(analysis) {
+            Some(rho) => println!("  Length-frequency correlation: {} (Spearman)", fixed(config, rho, 3)),
+            None => println!("  Length-frequency correlation: n/a"),
+        },
+        // Unknown for sessions and resumed checkpoints, so left out
+        Stat::Lines => {
+            if let Some(s) = &analysis.structure {
+                println!("  Lines: {} ({} blank)", s.lines, s.blank_lines);
+            }
+        }
+        Stat::Paragraphs => {
+            if let Some(s) = &analysis.structure {
+                println!(
+                    "  Paragraphs: {} ({} {}s per paragraph)",
+                    s.paragraphs,
+                    fixed(config, s.words_per_paragraph(), 1),
+                    unit
+                );
+            }
+        }
+        Stat::Hapax => {
+            let hapax = freq::hapax(analysis);
+            let share = hapax.len() as f64 / analysis.freq.len().max(1) as f64 * 100.0;
+            println!("  Hapax legomena: {} ({}% of unique {}s)", hapax.len(), fixed(config, share, 1), unit);
+        }
+        Stat::Diversity => match diversity::measure(analysis) {
+            Some(d) => {
+                println!("  Type-token ratio: {}", fixed(config, d.ttr, 3));
+                if let Some(mattr) = d.mattr {
+                    println!("  Moving-average TTR ({} tokens): {}", diversity::WINDOW, fixed(config, mattr, 3));
+                }
+                println!("  Guiraud's R: {}", fixed(config, d.guiraud, 2));
+                if let Some(herdan) = d.herdan {
+                    println!("  Herdan's C: {}", fixed(config, herdan, 3));
+                }
+                println!("  Yule's K: {}", fixed(config, d.yule_k, 2));
+            }
+            None => println!("  Lexical diversity: no {}s", unit),
+        },
+        Stat::Top(n) => {
+            let mut out = BufWriter::new(io::stdout().lock());
+            let mut rows = || -> io::Result<()> {
+                writeln!(out, "  Top {} {}s:", n, unit)?;
+                let top: Vec<(&String, u32)> = analysis.reported(config.min_count).into_iter().take(n).collect();
+                let max = top.first().map_or(0, |w| w.1);
+                // Borrowed unless redacted; rows are written straight out
+                let rows: Vec<(Cow<str>, u32)> = ordered(config, top)
+                    .into_iter()
+                    .map(|(word, count)| (shown(redactor, word), count))
+                    .collect();
+                // Shown width of "word (count)": digits plus the space and brackets
+                let row_width = |(word, count): &(Cow<str>, u32)| word_length(word) + count.checked_ilog10().unwrap_or(0) as usize + 4;
+                // With `--chart`, rows are padded so the bars line up
+                let width = match config.chart {
+                    true => rows.iter().map(row_width).max().unwrap_or(0),
+                    false => 0,
+                };
+                for (rank, row) in rows.iter().enumerate() {
+                    write!(out, "    {:>3}. {} ({})", rank + 1, row.0, row.1)?;
+                    if config.chart {
+                        write!(out, "{:pad$} {}", "", chart::bar(row.1, max), pad = width - row_width(row))?;
+                    }
+                    writeln!(out)?;
+                }
+                out.flush()
+            };
+            rows().expect("failed printing to stdout");
+        }
+    }
+}
+
+// Headline words, `--leaders` of them; they can skip stopwords, while
+// totals stay unfiltered
+fn leaders<'a>(config: &Config, analysis: &'a AnalysisResult) -> Vec<(&'a String, u32)> {
+    let mut words: Vec<(&String, u32)> = analysis
+        .freq
+        .iter()
+        .filter(|&(word, _)| !config.content_words_only || !stopwords::is_stopword(word))
+        .map(|(word, &count)| (word, count))
+        .collect();
+    words.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    words.truncate(config.leaders.unwrap_or(1).max(1));
+    words
+}
+
+// Inputs that held only whitespace, in the order given
+fn empty_files(analysis: &AnalysisResult) -> Vec<&str> {
+    analysis
+        .files
+        .iter()
+        .filter(|f| f.status == FileStatus::Empty)
+        .map(|f| f.path.as_str())
+        .collect()
+}
+
+// Inputs `--dedup-docs` left out, with the input each one repeats
+fn duplicate_files(analysis: &AnalysisResult) -> Vec<(&str, &str, f64)> {
+    analysis
+        .files
+        .iter()
+        .filter_map(|f| match &f.status {
+            FileStatus::Duplicate { of, similarity } => Some((f.path.as_str(), of.as_str(), *similarity)),
+            _ => None,
+        })
+        .collect()
+}
+
+// Share of all counted words
+fn percent(analysis: &AnalysisResult, count: u32) -> f64 {
+    count as f64 / analysis.total_words().max(1) as f64 * 100.0
+}
+
+// `--format json`: the filters and stats block as one object on stdout,
+// with the run metadata
+pub fn print_json(config: &Config, analysis: &AnalysisResult, meta: &RunMetadata) {
+    let redactor = config.redact.as_ref();
+    let word = |w: &str| json::string(&shown(redactor, w));
+    let fields: Vec<String> = selected_stats(config)
+        .into_iter()
+        .map(|stat| match stat {
+            Stat::Total => format!("\"total_words\": {}", analysis.total_words()),
+            Stat::Unique => format!("\"unique_words\": {}", analysis.freq.len()),
+            Stat::Average => format!("\"average_length\": {}", fixed(config, average_length(analysis), 2)),
+            Stat::MostCommon => {
+                let leaders: Vec<String> = leaders(config, analysis)
+                    .into_iter()
+                    .map(|(w, count)| {
+                        format!(
+                            "{{\"word\": {}, \"count\": {}, \"percent\": {}}}",
+                            word(w),
+                            count,
+                            fixed(config, percent(analysis, count), 1)
+                        )
+                    })
+                    .collect();
+                // `most_common` keeps its shape; `--leaders` adds the list as a key of its own
+                let most_common = leaders.first().map_or("null", String::as_str);
+                match config.leaders {
+                    Some(_) => format!("\"most_common\": {},\n    \"leaders\": [{}]", most_common, leaders.join(", ")),
+                    None => format!("\"most_common\": {}", most_common),
+                }
+            }
+            Stat::Entropy => format!("\"entropy\": {}", fixed(config, entropy(analysis), 3)),
+            Stat::Brevity => match brevity::correlation(analysis) {
+                Some(rho) => format!("\"brevity\": {}", fixed(config, rho, 3)),
+                None => "\"brevity\": null".to_string(),
+            },
+            Stat::Lines => match &analysis.structure {
+                Some(s) => format!("\"lines\": {{\"total\": {}, \"blank\": {}}}", s.lines, s.blank_lines),
+                None => "\"lines\": null".to_string(),
+            },
+            Stat::Paragraphs => match &analysis.structure {
+                Some(s) => format!(
+                    "\"paragraphs\": {{\"count\": {}, \"words_per_paragraph\": {}}}",
+                    s.paragraphs,
+                    fixed(config, s.words_per_paragraph(), 1)
+                ),
+                None => "\"paragraphs\": null".to_string(),
+            },
+            Stat::Hapax => {
+                let hapax = freq::hapax(analysis);
+                let share = hapax.len() as f64 / analysis.freq.len().max(1) as f64 * 100.0;
+                let words = match config.hapax {
+                    true => {
+                        let words: Vec<String> = hapax.iter().map(|w| word(w)).collect();
+                        format!(", \"words\": [{}]", words.join(", "))
+                    }
+                    false => String::new(),
+                };
+                format!(
+                    "\"hapax\": {{\"count\": {}, \"percent_of_unique\": {}{}}}",
+                    hapax.len(),
+                    fixed(config, share, 1),
+                    words
+                )
+            }
+            Stat::Diversity => match diversity::measure(analysis) {
+                Some(d) => {
+                    let optional = |v: Option<f64>, digits| v.map_or("null".to_string(), |v| fixed(config, v, digits));
+                    format!(
+                        "\"diversity\": {{\"ttr\": {}, \"mattr\": {}, \"guiraud\": {}, \"herdan\": {}, \"yule_k\": {}}}",
+                        fixed(config, d.ttr, 3),
+                        optional(d.mattr, 3),
+                        fixed(config, d.guiraud, 2),
+                        optional(d.herdan, 3),
+                        fixed(config, d.yule_k, 2)
+                    )
+                }
+                None => "\"diversity\": null".to_string(),
+            },
+            Stat::Top(n) => {
+                let top = analysis.reported(config.min_count).into_iter().take(n).collect();
+                let top: Vec<String> = ordered(config, top)
+                    .into_iter()
+                    .map(|(w, count)| format!("{{\"word\": {}, \"count\": {}}}", word(w), count))
+                    .collect();
+                format!("\"top\": [{}]", top.join(", "))
+            }
+        })
+        .collect();
+    let starts_with = config
+        .starts_with
+        .map_or("null".to_string(), |c| json::string(&c.to_string()));
+    let optional = |s: &Option<String>| s.as_deref().map_or("null".to_string(), json::string);
+
+    println!("{{");
+    println!("  \"file\": {},", json::string(&config.file_path));
+    let files: Vec<String> = config.files.iter().map(|f| json::string(f)).collect();
+    println!("  \"files\": [{}],", files.join(", "));
+    let empty = empty_files(analysis);
+    if !empty.is_empty() {
+        let empty: Vec<String> = empty.into_iter().map(json::string).collect();
+        println!("  \"empty_files\": [{}],", empty.join(", "));
+    }
+    let duplicates: Vec<String> = duplicate_files(analysis)
+        .into_iter()
+        .map(|(path, of, similarity)| {
+            format!(
+                "{{\"path\": {}, \"of\": {}, \"similarity\": {}}}",
+                json::string(path),
+                json::string(of),
+                fixed(config, similarity, 3)
+            )
+        })
+        .collect();
+    if !duplicates.is_empty() {
+        println!("  \"duplicates\": [{}],", duplicates.join(", "));
+    }
+    println!(
+        "  \"filters\": {{\"min_length\": {}, \"min_count\": {}, \"starts_with\": {}, \"ends_with\": {}, \"contains\": {}, \"content_words_only\": {}}},",
+        config.min_length,
+        config.min_count,
+        starts_with,
+        optional(&config.ends_with),
+        optional(&config.contains),
+        config.content_words_only
+    );
+    println!("  \"stats\": {{");
+    println!("    {}", fields.join(",\n    "));
+    println!("  }},");
+    if let Some(r) = &analysis.readability {
+        println!(
+            "  \"readability\": {{\"reading_ease\": {}, \"grade\": {}, \"fog\": {}}},",
+            fixed(config, r.ease(), 1),
+            fixed(config, r.grade(), 1),
+            fixed(config, r.fog(), 1)
+        );
+    }
+    println!("  \"metadata\": {}", meta.json());
+    println!("}}");
+}
+
+// `value` to `--precision` decimals, or the stat's own default
+pub(crate) fn fixed(config: &Config, value: f64, default: usize) -> String {
+    format!("{:.*}", config.precision.unwrap_or(default), value)
+}
+
+// Stats (#6: Sum)
+fn average_length(analysis: &AnalysisResult) -> f64 {
+    let total_words = analysis.total_words();
+    if total_words > 0 {
+        analysis.sum_length as f64 / total_words as f64
+    } else {
+        0.0
+    }
+}
+
+// Shannon entropy of the word distribution
+fn entropy(analysis: &AnalysisResult) -> f64 {
+    let total = analysis.total_words() as f64;
+    analysis
+        .freq
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+// Per-word table: count plus first/last occurrence as token index and line
+fn print_detailed(config: &Config, analysis: &AnalysisResult) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    // Sniffed again here, from the same first bytes reading decoded with
+    let files: Vec<&String> = config.files.iter().filter(|f| *f != STDIN_PATH).collect();
+    if !files.is_empty() {
+        writeln!(out, "\nEncodings:")?;
+        for file in files {
+            match encoding::sniff(file) {
+                Ok(found) => writeln!(out, "  {}: {}", file, found.name())?,
+                Err(e) => writeln!(out, "  {}: unknown ({})", file, e)?,
+            }
+        }
+    }
+    writeln!(out, "\nDetailed:")?;
+    writeln!(out, "  {:<20} {:>7} {:>16} {:>16}", unit(config), "count", "first (tok:line)", "last (tok:line)")?;
+    for (word, count) in ordered(config, analysis.reported(config.min_count)) {
+        let positions = analysis.positions.get(word);
+        let word = shown(config.redact.as_ref(), word);
+        match positions {
+            Some(o) => writeln!(out, "  {:<20} {:>7} {:>16} {:>16}", word, count, o.first, o.last)?,
+            None => writeln!(out, "  {:<20} {:>7} {:>16} {:>16}", word, count, "-", "-")?,
+        }
+    }
+    out.flush()
+}
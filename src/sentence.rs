@@ -0,0 +1,209 @@
+// Sentence segmentation: a sentence ends at `.`, `!` or `?` (plus any
+// closing quotes or brackets) followed by whitespace or the end of text.
+// A period inside a token ("3.14", "e.g") never ends a sentence, nor does
+// a quoted "!" or "?" followed by a lowercase word; the rules below tune the
+// rest for legal and scientific texts.
+
+use crate::Config;
+use crate::redact::shown_text;
+use crate::report::fixed;
+
+const DEFAULT_ABBREVIATIONS: &[&str] = &[
+    "al", "approx", "cf", "dept", "dr", "e.g", "eq", "esp", "et", "etc", "fig", "i.e", "inc",
+    "jr", "ltd", "mr", "mrs", "ms", "no", "p", "pp", "prof", "sr", "st", "vs",
+];
+
+#[derive(Clone)]
+pub struct SentenceRules {
+    // Lowercased, without the trailing period
+    pub abbreviations: Vec<String>,
+    pub newline_boundary: bool,
+    pub ellipsis_boundary: bool,
+}
+
+impl Default for SentenceRules {
+    fn default() -> Self {
+        SentenceRules {
+            abbreviations: DEFAULT_ABBREVIATIONS.iter().map(|a| a.to_string()).collect(),
+            newline_boundary: false,
+            ellipsis_boundary: false,
+        }
+    }
+}
+
+impl SentenceRules {
+    // One abbreviation per line, with or without its period ("Art." or "art")
+    pub fn add_abbreviations(&mut self, list: &str) {
+        let extra = list
+            .lines()
+            .map(|l| l.trim().trim_end_matches('.').to_lowercase())
+            .filter(|l| !l.is_empty());
+        self.abbreviations.extend(extra);
+    }
+
+    pub(crate) fn is_abbreviation(&self, word: &str) -> bool {
+        let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+        // Single-letter initials ("J. Smith") are never boundaries
+        word.chars().count() == 1 && word.chars().all(char::is_alphabetic)
+            || self.abbreviations.iter().any(|a| a.eq_ignore_ascii_case(word))
+    }
+}
+
+pub fn split<'a>(text: &'a str, rules: &SentenceRules) -> Vec<&'a str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '\n' && rules.newline_boundary {
+            push(&mut sentences, &text[start..i]);
+            start = i;
+            continue;
+        }
+        if !matches!(c, '.' | '!' | '?' | '…') {
+            continue;
+        }
+        // Swallow runs like "?!", "..." or ".)" before deciding
+        let mut dots = usize::from(c == '.');
+        let mut ellipsis = c == '…';
+        let mut quoted = false;
+        while let Some(&(_, next)) = chars.peek() {
+            if matches!(next, '.' | '!' | '?' | '…' | '"' | '\'' | ')' | ']' | '”' | '’') {
+                dots += usize::from(next == '.');
+                ellipsis |= next == '…';
+                quoted |= matches!(next, '"' | '\'' | '”' | '’');
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        let end = chars.peek().map_or(text.len(), |&(j, _)| j);
+        if chars.peek().is_some_and(|&(_, next)| !next.is_whitespace()) {
+            continue;
+        }
+        if (ellipsis || dots >= 3) && !rules.ellipsis_boundary {
+            continue;
+        }
+        let next_lowercase = text[end..].trim_start().starts_with(char::is_lowercase);
+        // A quotation ending in "!" or "?" mid-sentence: "Stop!" she said
+        if quoted && next_lowercase {
+            continue;
+        }
+        if c == '.' && dots == 1 {
+            let word = text[start..i].rsplit(char::is_whitespace).next().unwrap_or("");
+            if rules.is_abbreviation(word) || is_initialism(word) && next_lowercase {
+                continue;
+            }
+        }
+        push(&mut sentences, &text[start..end]);
+        start = end;
+    }
+    push(&mut sentences, &text[start..]);
+    sentences
+}
+
+// "U.S" or "a.m": single letters joined by periods. Such a word ends a
+// sentence only when a capitalized word follows.
+fn is_initialism(word: &str) -> bool {
+    let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    let letter = |part: &str| part.chars().count() == 1 && part.chars().all(char::is_alphabetic);
+    word.contains('.') && word.split('.').all(letter)
+}
+
+fn push<'a>(sentences: &mut Vec<&'a str>, candidate: &'a str) {
+    let candidate = candidate.trim();
+    if candidate.chars().any(char::is_alphanumeric) {
+        sentences.push(candidate);
+    }
+}
+
+// Sentence counts for `--sentence-stats`; words split on whitespace
+pub struct SentenceStats {
+    pub sentences: usize,
+    pub words: usize,
+    // (word count, sentence) of the longest and shortest, first one on ties
+    pub longest: (usize, String),
+    pub shortest: (usize, String),
+}
+
+// Characters of a sentence shown in the report
+const SHOWN_CHARS: usize = 60;
+
+pub fn stats(text: &str, rules: &SentenceRules) -> SentenceStats {
+    let mut stats = SentenceStats {
+        sentences: 0,
+        words: 0,
+        longest: (0, String::new()),
+        shortest: (usize::MAX, String::new()),
+    };
+    for sentence in split(text, rules) {
+        let words: Vec<&str> = sentence.split_whitespace().collect();
+        stats.sentences += 1;
+        stats.words += words.len();
+        if words.len() > stats.longest.0 {
+            stats.longest = (words.len(), words.join(" "));
+        }
+        if words.len() < stats.shortest.0 {
+            stats.shortest = (words.len(), words.join(" "));
+        }
+    }
+    if stats.sentences == 0 {
+        stats.shortest.0 = 0;
+    }
+    stats
+}
+
+pub fn print_stats(config: &Config, stats: &SentenceStats) {
+    // Redacted whole, so a clipped email or name is still masked
+    let clip = |s: &str| {
+        let s = shown_text(config.redact.as_ref(), s);
+        match s.char_indices().nth(SHOWN_CHARS) {
+            Some((end, _)) => format!("{}...", &s[..end]),
+            None => s.into_owned(),
+        }
+    };
+    println!("\nSentences:");
+    println!("  Count: {}", stats.sentences);
+    if stats.sentences == 0 {
+        return;
+    }
+    println!("  Average words per sentence: {}", fixed(config, stats.words as f64 / stats.sentences as f64, 1));
+    println!("  Longest: {} words \"{}\"", stats.longest.0, clip(&stats.longest.1));
+    println!("  Shortest: {} words \"{}\"", stats.shortest.0, clip(&stats.shortest.1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentences(text: &str) -> Vec<&str> {
+        split(text, &SentenceRules::default())
+    }
+
+    #[test]
+    fn ends_at_terminal_punctuation() {
+        assert_eq!(sentences("One. Two?! \"Three.\" (Four.) Five"), ["One.", "Two?!", "\"Three.\"", "(Four.)", "Five"]);
+        assert_eq!(sentences("Pi is 3.14 here, e.g. now. Done."), ["Pi is 3.14 here, e.g. now.", "Done."]);
+    }
+
+    #[test]
+    fn abbreviations_and_initials_do_not_end_sentences() {
+        assert_eq!(sentences("Dr. Smith met J. Doe at St. Paul. Then left."), ["Dr. Smith met J. Doe at St. Paul.", "Then left."]);
+        assert_eq!(sentences("At 9 a.m. we met. In the U.S. People vote."), ["At 9 a.m. we met.", "In the U.S.", "People vote."]);
+    }
+
+    #[test]
+    fn quoted_exclamations_mid_sentence_continue() {
+        assert_eq!(sentences("\"Stop!\" she said. \"Go!\" Then quiet."), ["\"Stop!\" she said.", "\"Go!\"", "Then quiet."]);
+    }
+
+    #[test]
+    fn rules_add_abbreviations_and_boundaries() {
+        let mut rules = SentenceRules::default();
+        rules.add_abbreviations("Art.\nsec\n");
+        assert_eq!(split("See Art. 5 and sec. 2. Next.", &rules), ["See Art. 5 and sec. 2.", "Next."]);
+        assert_eq!(sentences("Wait... then go."), ["Wait... then go."]);
+        rules.ellipsis_boundary = true;
+        rules.newline_boundary = true;
+        assert_eq!(split("Wait... then go\nA heading", &rules), ["Wait...", "then go", "A heading"]);
+    }
+}
@@ -0,0 +1,71 @@
+// `--sentence-types`: declarative / question / exclamation / imperative
+// proportions. A sentence is imperative when it opens with a bare verb from
+// the list below (or "please", "let's", "don't"); the check runs before the
+// exclamation one, so "Send it now!" counts as a command.
+
+use crate::Config;
+use crate::report::fixed;
+use crate::sentence;
+
+// Common base-form verbs that open commands, kept sorted for binary search
+const IMPERATIVE_VERBS: &[&str] = &[
+    "add", "allow", "apply", "ask", "be", "bring", "call", "cancel", "change", "check", "choose",
+    "click", "close", "come", "confirm", "consider", "contact", "continue", "create", "delete",
+    "do", "download", "email", "enable", "ensure", "enter", "fill", "find", "fix", "follow",
+    "forward", "get", "give", "go", "help", "include", "install", "keep", "leave", "let", "look",
+    "make", "note", "open", "pay", "place", "provide", "put", "read", "refund", "remember",
+    "remove", "reply", "report", "reset", "restart", "return", "review", "run", "see", "select",
+    "send", "set", "share", "show", "sign", "start", "stop", "submit", "take", "tell", "try",
+    "turn", "update", "upgrade", "use", "verify", "visit", "wait", "write",
+];
+
+#[derive(Default)]
+pub struct SentenceTypes {
+    pub declarative: usize,
+    pub question: usize,
+    pub exclamation: usize,
+    pub imperative: usize,
+}
+
+fn is_imperative(sentence: &str) -> bool {
+    let first = sentence
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+        .to_lowercase()
+        .replace('’', "'");
+    matches!(first.as_str(), "please" | "let's" | "don't" | "never")
+        || IMPERATIVE_VERBS.binary_search(&first.as_str()).is_ok()
+}
+
+pub fn classify(text: &str, config: &Config) -> SentenceTypes {
+    let mut types = SentenceTypes::default();
+    for s in sentence::split(text, &config.sentence_rules) {
+        let end = s.trim_end_matches(['"', '\'', ')', ']', '”', '’']);
+        if end.ends_with('?') {
+            types.question += 1;
+        } else if is_imperative(s) {
+            types.imperative += 1;
+        } else if end.ends_with('!') {
+            types.exclamation += 1;
+        } else {
+            types.declarative += 1;
+        }
+    }
+    types
+}
+
+pub fn print(config: &Config, types: &SentenceTypes) {
+    let total = types.declarative + types.question + types.exclamation + types.imperative;
+    println!("\nSentence types:");
+    for (name, count) in [
+        ("Declarative", types.declarative),
+        ("Question", types.question),
+        ("Exclamation", types.exclamation),
+        ("Imperative", types.imperative),
+    ] {
+        let share = if total == 0 { 0.0 } else { count as f64 * 100.0 / total as f64 };
+        println!("  {:<12} {} ({}%)", format!("{}:", name), count, fixed(config, share, 1));
+    }
+}
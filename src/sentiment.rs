@@ -0,0 +1,89 @@
+// Lexicon-based sentiment and its arc over the document: the token stream
+// is cut into ten equal slices and each slice gets its mean word score.
+
+use crate::Config;
+use crate::report::fixed;
+
+const SLICES: usize = 10;
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// Small AFINN-style lexicon, scores from -3 to 3. Kept sorted for lookups.
+const LEXICON: &[(&str, i32)] = &[
+    ("abandon", -2), ("afraid", -2), ("agree", 1), ("amazing", 3), ("anger", -3),
+    ("angry", -3), ("annoyed", -2), ("awful", -3), ("bad", -3), ("beautiful", 3),
+    ("best", 3), ("better", 2), ("bitter", -2), ("bless", 2), ("brave", 2),
+    ("bright", 1), ("broken", -1), ("calm", 2), ("care", 2), ("cheer", 2),
+    ("cold", -1), ("cruel", -3), ("cry", -1), ("damn", -3), ("danger", -2),
+    ("dark", -1), ("dead", -3), ("death", -2), ("delight", 3), ("despair", -3),
+    ("die", -3), ("disaster", -2), ("dread", -2), ("enjoy", 2), ("evil", -3),
+    ("excellent", 3), ("fail", -2), ("failure", -2), ("fear", -2), ("fine", 2),
+    ("fond", 2), ("free", 1), ("friend", 1), ("fun", 3), ("gentle", 2),
+    ("glad", 3), ("good", 3), ("great", 3), ("grief", -2), ("happy", 3),
+    ("hate", -3), ("help", 2), ("hope", 2), ("horrible", -3), ("hurt", -2),
+    ("ill", -2), ("joy", 3), ("kill", -3), ("kind", 2), ("laugh", 1),
+    ("lonely", -2), ("lose", -3), ("lost", -3), ("love", 3), ("lovely", 3),
+    ("luck", 3), ("mad", -3), ("miserable", -3), ("nice", 3), ("pain", -2),
+    ("peace", 2), ("perfect", 3), ("pleasant", 3), ("poor", -2), ("pretty", 1),
+    ("problem", -2), ("proud", 2), ("sad", -2), ("safe", 1), ("scared", -2),
+    ("shame", -2), ("sick", -2), ("smile", 2), ("sorrow", -2), ("sorry", -1),
+    ("strong", 2), ("success", 2), ("suffer", -2), ("sweet", 2), ("terrible", -3),
+    ("thank", 2), ("ugly", -3), ("unhappy", -2), ("warm", 1), ("weak", -2),
+    ("win", 3), ("wonderful", 3), ("worry", -3), ("worse", -3), ("worst", -3),
+    ("wrong", -2),
+];
+
+pub fn score(word: &str) -> i32 {
+    let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+    LEXICON
+        .binary_search_by(|(w, _)| w.cmp(&word))
+        .map_or(0, |i| LEXICON[i].1)
+}
+
+pub struct SentimentArc {
+    pub overall: f64,
+    pub slices: Vec<f64>,
+}
+
+// Mean score per word overall and for each tenth of the document
+pub fn arc(text: &str) -> SentimentArc {
+    let scores: Vec<i32> = text.split_whitespace().map(|w| score(&w.to_lowercase())).collect();
+    let mean = |s: &[i32]| {
+        if s.is_empty() {
+            0.0
+        } else {
+            s.iter().sum::<i32>() as f64 / s.len() as f64
+        }
+    };
+    let slices = (0..SLICES)
+        .map(|i| mean(&scores[i * scores.len() / SLICES..(i + 1) * scores.len() / SLICES]))
+        .collect();
+    SentimentArc { overall: mean(&scores), slices }
+}
+
+fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|&v| {
+            if max > min {
+                BARS[((v - min) / (max - min) * (BARS.len() - 1) as f64).round() as usize]
+            } else {
+                BARS[BARS.len() / 2]
+            }
+        })
+        .collect()
+}
+
+pub fn print(config: &Config, arc: &SentimentArc) {
+    // Signed like `{:+}`, at the configured precision
+    let signed = |v: f64, digits| {
+        let v = fixed(config, v, digits);
+        if v.starts_with('-') { v } else { format!("+{}", v) }
+    };
+    println!("\nSentiment:");
+    println!("  Overall: {} per word", signed(arc.overall, 3));
+    println!("  Arc by decile: {}", sparkline(&arc.slices));
+    let values: Vec<String> = arc.slices.iter().map(|&v| signed(v, 2)).collect();
+    println!("  Decile scores: {}", values.join(" "));
+}
@@ -0,0 +1,256 @@
+// Session files: a binary snapshot of an analysis (options + counted state)
+// so follow-up queries can run without re-reading the source text.
+//
+// Layout (little-endian): magic, version, the input path and the counting
+// options (`Config::counting_options`), then the frequency map with each
+// word's first/last occurrence. Loading with other counting options is
+// refused, since the stored counts were filtered under the saved ones.
+// Strings are stored as a u32 byte length followed by UTF-8 bytes. Files
+// are read whole and every length is checked against the bytes left, so a
+// truncated or crafted file is an error rather than a huge allocation.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+
+use crate::hasher::{HasherKind, WordState};
+use crate::{AnalysisResult, Config, Occurrences, Position};
+
+const MAGIC: &[u8; 4] = b"TASN";
+const VERSION: u32 = 3;
+
+// Smallest stored frequency entry: an empty word, its count and two positions
+const ENTRY_BYTES: usize = 4 + 4 + 4 * 8;
+
+pub fn save(path: &str, config: &Config, analysis: &AnalysisResult) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(MAGIC)?;
+    write_u32(&mut out, VERSION)?;
+
+    // Options the counts were produced with
+    write_str(&mut out, &config.file_path)?;
+    write_str(&mut out, &config.counting_options())?;
+
+    write_counts(&mut out, analysis)?;
+    out.flush()
+}
+
+// Counted state, sorted so identical inputs give identical files
+pub(crate) fn write_counts(out: &mut impl Write, analysis: &AnalysisResult) -> io::Result<()> {
+    write_u64(out, analysis.sum_length as u64)?;
+    let mut entries: Vec<(&String, &u32)> = analysis.freq.iter().collect();
+    entries.sort();
+    write_u64(out, entries.len() as u64)?;
+    for (word, &count) in entries {
+        write_str(out, word)?;
+        write_u32(out, count)?;
+        let o = analysis.positions.get(word).copied().unwrap_or(Occurrences {
+            first: Position { token: 0, line: 0 },
+            last: Position { token: 0, line: 0 },
+        });
+        for p in [o.first, o.last] {
+            write_u64(out, p.token as u64)?;
+            write_u64(out, p.line as u64)?;
+        }
+    }
+    Ok(())
+}
+
+// Restores the stored input path into `config` and returns the stored
+// analysis; fails when `config` counts under other options
+pub fn load(path: &str, config: &mut Config) -> io::Result<AnalysisResult> {
+    let bytes = fs::read(path)?;
+    let mut input = bytes.as_slice();
+    let mut magic = [0u8; 4];
+    input.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid("not a session file"));
+    }
+    let version = read_u32(&mut input)?;
+    if version != VERSION {
+        return Err(invalid(&format!("unsupported session version {}", version)));
+    }
+
+    let file_path = read_str(&mut input)?;
+    check_options(&read_str(&mut input)?, config)?;
+    config.file_path = file_path;
+
+    read_counts(&mut input, config.hasher)
+}
+
+// Stored counting options against the ones in effect now
+pub(crate) fn check_options(stored: &str, config: &Config) -> io::Result<()> {
+    let current = config.counting_options();
+    if stored == current {
+        return Ok(());
+    }
+    let shown = |options: &str| match options {
+        "" => "no counting options".to_string(),
+        options => options.to_string(),
+    };
+    Err(invalid(&format!(
+        "counted with {}, but this run uses {}",
+        shown(stored),
+        shown(&current)
+    )))
+}
+
+pub(crate) fn read_counts(input: &mut &[u8], hasher: HasherKind) -> io::Result<AnalysisResult> {
+    let sum_length = read_u64(input)? as usize;
+    let len = read_u64(input)? as usize;
+    if len > input.len() / ENTRY_BYTES {
+        return Err(invalid("word count exceeds the file size"));
+    }
+    let mut freq = HashMap::with_capacity_and_hasher(len, WordState::new(hasher));
+    let mut positions = HashMap::with_capacity_and_hasher(len, WordState::new(hasher));
+    for _ in 0..len {
+        let word = read_str(input)?;
+        let count = read_u32(input)?;
+        let first = read_position(input)?;
+        let last = read_position(input)?;
+        positions.insert(word.clone(), Occurrences { first, last });
+        freq.insert(word, count);
+    }
+    Ok(AnalysisResult {
+        freq,
+        positions,
+        sum_length,
+        ..AnalysisResult::default()
+    })
+}
+
+pub(crate) fn invalid(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.to_string())
+}
+
+pub(crate) fn write_u32(out: &mut impl Write, value: u32) -> io::Result<()> {
+    out.write_all(&value.to_le_bytes())
+}
+
+pub(crate) fn write_u64(out: &mut impl Write, value: u64) -> io::Result<()> {
+    out.write_all(&value.to_le_bytes())
+}
+
+pub(crate) fn write_str(out: &mut impl Write, value: &str) -> io::Result<()> {
+    write_u32(out, value.len() as u32)?;
+    out.write_all(value.as_bytes())
+}
+
+pub(crate) fn read_u32(input: &mut &[u8]) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    input.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+pub(crate) fn read_u64(input: &mut &[u8]) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    input.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_position(input: &mut &[u8]) -> io::Result<Position> {
+    let token = read_u64(input)? as usize;
+    let line = read_u64(input)? as usize;
+    Ok(Position { token, line })
+}
+
+pub(crate) fn read_str(input: &mut &[u8]) -> io::Result<String> {
+    let len = read_u32(input)? as usize;
+    if len > input.len() {
+        return Err(invalid("string length exceeds the file size"));
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    String::from_utf8(bytes.to_vec()).map_err(|_| invalid("invalid UTF-8 in string"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analysis(config: &Config) -> AnalysisResult {
+        let mut analysis = AnalysisResult::with_hasher(config.hasher);
+        for (token, word) in ["the", "cat", "the"].into_iter().enumerate() {
+            let at = Position { token: token + 1, line: 1 };
+            *analysis.freq.entry(word.to_string()).or_insert(0) += 1;
+            analysis
+                .positions
+                .entry(word.to_string())
+                .and_modify(|o| o.last = at)
+                .or_insert(Occurrences { first: at, last: at });
+        }
+        analysis.sum_length = 9;
+        analysis
+    }
+
+    fn scratch(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("text_analyzer-{}-{}", std::process::id(), name));
+        path.to_string_lossy().into_owned()
+    }
+
+    fn counts(analysis: &AnalysisResult) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_counts(&mut bytes, analysis).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn a_saved_session_loads_back() {
+        let config = Config { file_path: "notes.txt".to_string(), ..Config::default() };
+        let path = scratch("round-trip.tasn");
+        save(&path, &config, &analysis(&config)).unwrap();
+
+        let mut loaded_config = Config::default();
+        let loaded = load(&path, &mut loaded_config).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded_config.file_path, "notes.txt");
+        assert_eq!(loaded.sum_length, 9);
+        assert_eq!((loaded.freq["the"], loaded.freq["cat"]), (2, 1));
+        let the = loaded.positions["the"];
+        assert_eq!((the.first.token, the.last.token), (1, 3));
+    }
+
+    #[test]
+    fn other_counting_options_are_refused() {
+        let path = scratch("options.tasn");
+        save(&path, &Config::default(), &analysis(&Config::default())).unwrap();
+        let mut config = Config { case_sensitive: true, ..Config::default() };
+        let error = load(&path, &mut config).err().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(error.to_string().contains("this run uses --case-sensitive"), "{}", error);
+    }
+
+    #[test]
+    fn every_truncation_is_an_error() {
+        let bytes = counts(&analysis(&Config::default()));
+        for len in 0..bytes.len() {
+            let mut input = &bytes[..len];
+            assert!(read_counts(&mut input, HasherKind::default()).is_err(), "read {} of {} bytes", len, bytes.len());
+        }
+        assert!(read_counts(&mut bytes.as_slice(), HasherKind::default()).is_ok());
+    }
+
+    #[test]
+    fn crafted_lengths_do_not_allocate() {
+        let mut bytes = Vec::new();
+        write_u64(&mut bytes, 0).unwrap();
+        write_u64(&mut bytes, u64::MAX).unwrap();
+        let error = read_counts(&mut bytes.as_slice(), HasherKind::default()).err().unwrap();
+        assert_eq!(error.to_string(), "word count exceeds the file size");
+
+        let mut bytes = Vec::new();
+        write_u32(&mut bytes, u32::MAX).unwrap();
+        bytes.extend_from_slice(b"short");
+        let error = read_str(&mut bytes.as_slice()).err().unwrap();
+        assert_eq!(error.to_string(), "string length exceeds the file size");
+    }
+
+    #[test]
+    fn other_files_are_not_sessions() {
+        let path = scratch("not-a-session");
+        fs::write(&path, b"TASX\x03\0\0\0").unwrap();
+        let error = load(&path, &mut Config::default()).err().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(error.to_string(), "not a session file");
+    }
+}
@@ -0,0 +1,57 @@
+// `--stem`: Snowball stemming before counting, so "run", "runs" and
+// "running" share one entry. The stemmer follows `--language`, English when
+// it is not given. Filters see the word as written; only the counted form
+// is stemmed. The report lists which surface forms each stem merged.
+
+use std::collections::HashMap;
+
+use rust_stemmers::{Algorithm, Stemmer};
+
+use crate::language::Language;
+use crate::redact::shown;
+use crate::Config;
+
+const SHOWN: usize = 20;
+
+// Stem -> surface form -> count
+pub type Variants = HashMap<String, HashMap<String, u32>>;
+
+type Forms<'a> = Vec<(&'a String, u32)>;
+
+pub fn stem(word: &str, language: Option<Language>) -> String {
+    let algorithm = match language.unwrap_or(Language::English) {
+        Language::English => Algorithm::English,
+        Language::Spanish => Algorithm::Spanish,
+        Language::German => Algorithm::German,
+        Language::French => Algorithm::French,
+    };
+    Stemmer::create(algorithm).stem(word).into_owned()
+}
+
+// Stems that merged more than one form, most frequent first, each with its
+// forms by count
+pub fn print_variants(config: &Config, variants: &Variants) {
+    let mut merged: Vec<(&String, u32, Forms)> = variants
+        .iter()
+        .filter(|(_, forms)| forms.len() > 1)
+        .map(|(stem, forms)| {
+            let mut forms: Forms = forms.iter().map(|(f, &c)| (f, c)).collect();
+            forms.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+            (stem, forms.iter().map(|f| f.1).sum(), forms)
+        })
+        .collect();
+    merged.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let redactor = config.redact.as_ref();
+    println!("\nStem variants:");
+    if merged.is_empty() {
+        println!("  No stem merged more than one form.");
+    }
+    for (stem, total, forms) in merged.into_iter().take(config.top.unwrap_or(SHOWN)) {
+        let forms: Vec<String> = forms
+            .into_iter()
+            .map(|(form, count)| format!("{} {}", shown(redactor, form), count))
+            .collect();
+        println!("  {} ({}): {}", shown(redactor, stem), total, forms.join(", "));
+    }
+}
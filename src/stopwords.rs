@@ -0,0 +1,126 @@
+// Built-in English stopword list (function words that dominate every text).
+// Kept sorted so lookups can binary search. `--stopwords en|FILE` drops the
+// built-in or a custom list from the counts; a file holds one word per line,
+// with `#` starting a comment.
+//
+// `--suggest-stopwords N` proposes a list for the corpus at hand: words
+// that are both widespread and frequent say little about any one document.
+// Each word scores its share of the documents (input files) times its share
+// of the tokens, so with a single document the ranking is by count alone.
+// Words on a stop list in use were never counted, so the suggestions extend
+// it. `--export-stopwords FILE` writes them in the format `--stopwords`
+// reads back.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::idf::DocumentFrequencies;
+use crate::metadata::RunMetadata;
+use crate::redact::shown;
+use crate::{AnalysisResult, Config};
+
+const ENGLISH: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "am", "an", "and", "any", "are",
+    "as", "at", "be", "because", "been", "before", "being", "below", "between", "both", "but",
+    "by", "can", "could", "did", "do", "does", "doing", "down", "during", "each", "few", "for",
+    "from", "further", "had", "has", "have", "having", "he", "her", "here", "hers", "herself",
+    "him", "himself", "his", "how", "i", "if", "in", "into", "is", "it", "its", "itself", "just",
+    "me", "more", "most", "my", "myself", "no", "nor", "not", "now", "of", "off", "on", "once",
+    "only", "or", "other", "our", "ours", "ourselves", "out", "over", "own", "same", "she",
+    "should", "so", "some", "such", "than", "that", "the", "their", "theirs", "them",
+    "themselves", "then", "there", "these", "they", "this", "those", "through", "to", "too",
+    "under", "until", "up", "very", "was", "we", "were", "what", "when", "where", "which",
+    "while", "who", "whom", "why", "will", "with", "would", "you", "your", "yours", "yourself",
+    "yourselves",
+];
+
+pub fn is_stopword(word: &str) -> bool {
+    ENGLISH.binary_search(&word).is_ok()
+}
+
+pub enum StopList {
+    English,
+    Custom(HashSet<String>),
+}
+
+impl StopList {
+    pub fn builtin(name: &str) -> Option<StopList> {
+        match name {
+            "en" | "english" => Some(StopList::English),
+            _ => None,
+        }
+    }
+
+    pub fn parse(list: &str) -> StopList {
+        let words = list
+            .lines()
+            .map(|l| l.split('#').next().unwrap_or("").trim().to_lowercase())
+            .filter(|w| !w.is_empty());
+        StopList::Custom(words.collect())
+    }
+
+    // Surrounding punctuation is ignored, so "the," is a stopword too
+    pub fn contains(&self, word: &str) -> bool {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        match self {
+            StopList::English => is_stopword(word),
+            StopList::Custom(words) => words.contains(word),
+        }
+    }
+}
+
+// Listed when only `--export-stopwords` is given
+pub const DEFAULT_SUGGESTIONS: usize = 50;
+
+pub struct Suggestions {
+    pub documents: usize,
+    // (word, documents it appears in, count), best candidates first
+    pub words: Vec<(String, u32, u32)>,
+}
+
+pub fn suggest(analysis: &AnalysisResult, frequencies: &DocumentFrequencies, n: usize) -> Suggestions {
+    let DocumentFrequencies { df, documents } = frequencies;
+    let documents = *documents;
+    let total = analysis.total_words().max(1) as f64;
+    let score = |word: &String, count: u32| {
+        let spread = df.get(word).copied().unwrap_or(0) as f64 / documents.max(1) as f64;
+        spread * count as f64 / total
+    };
+    let mut ranked: Vec<(&String, u32, f64)> = analysis
+        .freq
+        .iter()
+        .map(|(word, &count)| (word, count, score(word, count)))
+        .collect();
+    ranked.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(b.0)));
+    let words = ranked
+        .into_iter()
+        .take(n)
+        .map(|(word, count, _)| (word.clone(), df.get(word).copied().unwrap_or(0), count))
+        .collect();
+    Suggestions { documents, words }
+}
+
+pub fn print_suggestions(config: &Config, suggestions: &Suggestions) {
+    println!("\nSuggested stopwords:");
+    for (word, documents, count) in &suggestions.words {
+        println!(
+            "  {:<20} in {}/{} documents, count {}",
+            shown(config.redact.as_ref(), word),
+            documents,
+            suggestions.documents,
+            count
+        );
+    }
+}
+
+// One word per line after the `#` metadata lines, which `--stopwords` skips
+pub fn export(path: &str, config: &Config, suggestions: &Suggestions, meta: &RunMetadata) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    meta.write_comments(&mut out)?;
+    writeln!(out, "# documents: {}", suggestions.documents)?;
+    for (word, _, _) in &suggestions.words {
+        writeln!(out, "{}", shown(config.redact.as_ref(), word))?;
+    }
+    out.flush()
+}
@@ -0,0 +1,49 @@
+// Document structure for the stats block: lines, blank lines and
+// paragraphs (runs of non-blank lines), counted line by line as the
+// pipeline reads them.
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Structure {
+    pub lines: usize,
+    pub blank_lines: usize,
+    pub paragraphs: usize,
+    // Counted words, the ones left after the filters
+    pub words: usize,
+    // Whether the last line seen was blank (or nothing was seen yet)
+    in_gap: bool,
+}
+
+impl Structure {
+    pub fn new() -> Structure {
+        Structure { in_gap: true, ..Structure::default() }
+    }
+
+    pub fn add_line(&mut self, content: &str, words: usize) {
+        self.lines += 1;
+        self.words += words;
+        if content.trim().is_empty() {
+            self.blank_lines += 1;
+            self.in_gap = true;
+        } else {
+            self.paragraphs += usize::from(self.in_gap);
+            self.in_gap = false;
+        }
+    }
+
+    // Another input following this one; paragraphs never span inputs
+    pub fn merge(&mut self, other: Structure) {
+        self.lines += other.lines;
+        self.blank_lines += other.blank_lines;
+        self.paragraphs += other.paragraphs;
+        self.words += other.words;
+        self.in_gap = true;
+    }
+
+    pub fn words_per_paragraph(&self) -> f64 {
+        if self.paragraphs == 0 {
+            0.0
+        } else {
+            self.words as f64 / self.paragraphs as f64
+        }
+    }
+}
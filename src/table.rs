@@ -0,0 +1,57 @@
+// `--format table`: the inputs side by side, one column per file and one
+// row per headline stat, so documents compare at a glance. Ratios of an
+// empty or duplicate file show as "-". Needs the files themselves for the grade level.
+
+use crate::report::{fixed, unit};
+use crate::{AnalysisResult, Config, FileStats, FileStatus, word_length};
+
+// A row label and its value for one file
+type Row<'c> = (String, Box<dyn Fn(&FileStats) -> String + 'c>);
+
+fn rows(config: &Config) -> Vec<Row<'_>> {
+    let ratio = move |value: f64, digits, file: &FileStats| match file.status {
+        FileStatus::Counted => fixed(config, value, digits),
+        _ => "-".to_string(),
+    };
+    let unit = unit(config);
+    vec![
+        (format!("Total {}s", unit), Box::new(|f| f.tokens.to_string())),
+        (format!("Unique {}s", unit), Box::new(|f| f.types.to_string())),
+        ("Type-token ratio".to_string(), Box::new(move |f| ratio(f.types as f64 / f.tokens.max(1) as f64, 3, f))),
+        ("Average length".to_string(), Box::new(move |f| ratio(f.sum_length as f64 / f.tokens.max(1) as f64, 2, f))),
+        ("Hapax legomena".to_string(), Box::new(|f| f.hapax.to_string())),
+        ("Grade level".to_string(), Box::new(move |f| f.grade.map_or("-".to_string(), |g| fixed(config, g, 1)))),
+    ]
+}
+
+pub fn print(config: &Config, analysis: &AnalysisResult) {
+    let rows = rows(config);
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|(_, value)| analysis.files.iter().map(value).collect())
+        .collect();
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = analysis
+        .files
+        .iter()
+        .enumerate()
+        .map(|(column, file)| {
+            let values = cells.iter().map(|row| row[column].len());
+            values.chain([word_length(&file.path)]).max().unwrap_or(0)
+        })
+        .collect();
+
+    let mut header = " ".repeat(label_width);
+    for (file, &width) in analysis.files.iter().zip(&widths) {
+        let pad = width - word_length(&file.path);
+        header.push_str(&format!("  {}{}", " ".repeat(pad), file.path));
+    }
+    println!("{}", header);
+    for ((label, _), values) in rows.iter().zip(&cells) {
+        let mut line = format!("{:<w$}", label, w = label_width);
+        for (value, &width) in values.iter().zip(&widths) {
+            line.push_str(&format!("  {:>w$}", value, w = width));
+        }
+        println!("{}", line);
+    }
+}
@@ -0,0 +1,96 @@
+// `--temporal`: past / present / future orientation from word lists plus a
+// past-tense suffix heuristic ("-ed" on longer words). `--temporal-lexicon`
+// swaps the built-in lists for a file with one category per line, e.g.
+// `future will shall tomorrow`; lines naming another category are skipped.
+
+use crate::Config;
+use crate::report::fixed;
+
+const PAST: &[&str] = &[
+    "ago", "been", "came", "did", "earlier", "formerly", "gave", "got", "had", "knew", "last",
+    "left", "made", "previously", "said", "saw", "thought", "told", "took", "used", "was", "went",
+    "were", "yesterday",
+];
+const PRESENT: &[&str] = &[
+    "am", "are", "currently", "does", "has", "is", "now", "nowadays", "present", "presently",
+    "today", "tonight",
+];
+const FUTURE: &[&str] = &[
+    "forthcoming", "future", "gonna", "later", "next", "shall", "soon", "tomorrow", "upcoming",
+    "will", "won't",
+];
+
+// Words ending in "ed" that are not past tense
+const NOT_PAST: &[&str] = &[
+    "bed", "exceed", "hundred", "indeed", "naked", "need", "proceed", "sacred", "seed", "speed",
+    "succeed", "wicked",
+];
+
+#[derive(Clone)]
+pub struct Lexicon {
+    pub past: Vec<String>,
+    pub present: Vec<String>,
+    pub future: Vec<String>,
+}
+
+impl Default for Lexicon {
+    fn default() -> Self {
+        let owned = |list: &[&str]| list.iter().map(|w| w.to_string()).collect();
+        Lexicon { past: owned(PAST), present: owned(PRESENT), future: owned(FUTURE) }
+    }
+}
+
+impl Lexicon {
+    pub fn parse(list: &str) -> Lexicon {
+        let mut lexicon = Lexicon { past: Vec::new(), present: Vec::new(), future: Vec::new() };
+        for line in list.lines() {
+            let mut words = line.split_whitespace().map(str::to_lowercase);
+            let target = match words.next().as_deref().map(|c| c.trim_end_matches(':')) {
+                Some("past") => &mut lexicon.past,
+                Some("present") => &mut lexicon.present,
+                Some("future") => &mut lexicon.future,
+                _ => continue,
+            };
+            target.extend(words);
+        }
+        lexicon
+    }
+}
+
+#[derive(Default)]
+pub struct Orientation {
+    pub past: usize,
+    pub present: usize,
+    pub future: usize,
+}
+
+pub fn measure(text: &str, lexicon: &Lexicon) -> Orientation {
+    let mut o = Orientation::default();
+    for raw in text.split_whitespace() {
+        let word = raw
+            .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+            .to_lowercase()
+            .replace('’', "'");
+        let listed = |list: &[String]| list.contains(&word);
+        if listed(&lexicon.future) || word.ends_with("'ll") {
+            o.future += 1;
+        } else if listed(&lexicon.past)
+            || word.ends_with("'d")
+            || word.len() > 4 && word.ends_with("ed") && !NOT_PAST.contains(&word.as_str())
+        {
+            o.past += 1;
+        } else if listed(&lexicon.present) || word.ends_with("'m") || word.ends_with("'re") {
+            o.present += 1;
+        }
+    }
+    o
+}
+
+pub fn print(config: &Config, o: &Orientation) {
+    let total = o.past + o.present + o.future;
+    let share = |n: usize| fixed(config, if total == 0 { 0.0 } else { n as f64 * 100.0 / total as f64 }, 1);
+    println!("\nTemporal orientation:");
+    println!("  Past:    {} ({}%)", o.past, share(o.past));
+    println!("  Present: {} ({}%)", o.present, share(o.present));
+    println!("  Future:  {} ({}%)", o.future, share(o.future));
+}
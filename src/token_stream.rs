@@ -0,0 +1,44 @@
+// `--export-tokens FILE`: the counted token stream, after normalization and
+// filters. A `.jsonl` path gets one JSON object per token with its token
+// index, line and kind; any other path gets one token per line.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::redact::shown;
+use crate::{Config, json, tokens};
+
+// Apostrophes and hyphens inside words ("don't", "co-op") keep them words
+pub(crate) fn kind(token: &str) -> &'static str {
+    let joiner = |c: char| matches!(c, '\'' | '’' | '-');
+    if token.chars().any(char::is_alphabetic) && token.chars().all(|c| c.is_alphabetic() || joiner(c)) {
+        "word"
+    } else if token.chars().all(char::is_numeric) {
+        "number"
+    } else if token.chars().any(char::is_alphanumeric) {
+        "mixed"
+    } else {
+        "symbol"
+    }
+}
+
+pub fn export(path: &str, text: &str, config: &Config) -> io::Result<()> {
+    let jsonl = path.ends_with(".jsonl");
+    let mut out = BufWriter::new(File::create(path)?);
+    for (position, token) in tokens(text, config) {
+        let token = shown(config.redact.as_ref(), &token);
+        if jsonl {
+            writeln!(
+                out,
+                "{{\"token\": {}, \"index\": {}, \"line\": {}, \"kind\": \"{}\"}}",
+                json::string(&token),
+                position.token,
+                position.line,
+                kind(&token)
+            )?;
+        } else {
+            writeln!(out, "{}", token)?;
+        }
+    }
+    out.flush()
+}
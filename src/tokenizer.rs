@@ -0,0 +1,90 @@
+// Word segmentation, `--tokenizer`. Words follow Unicode word boundaries
+// (UAX #29) by default, so "word," counts as "word" and ideographs split
+// into words of their own; words joined by a single hyphen ("co-op") stay
+// one word, and so do email addresses and URLs, which word boundaries would
+// cut apart. `whitespace` keeps the older split on runs of whitespace.
+// `--token-pattern` overrides both.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Tokenizer {
+    #[default]
+    Unicode,
+    Whitespace,
+}
+
+impl Tokenizer {
+    pub fn parse(name: &str) -> Option<Tokenizer> {
+        match name {
+            "unicode" => Some(Tokenizer::Unicode),
+            "whitespace" => Some(Tokenizer::Whitespace),
+            _ => None,
+        }
+    }
+
+    pub fn split(self, content: &str) -> Box<dyn Iterator<Item = &str> + '_> {
+        match self {
+            Tokenizer::Unicode => Box::new(content.split_whitespace().flat_map(|chunk| {
+                let core = chunk.trim_matches(|c: char| !c.is_alphanumeric());
+                match is_address(core) {
+                    true => vec![core],
+                    false => hyphenated(chunk),
+                }
+            })),
+            Tokenizer::Whitespace => Box::new(content.split_whitespace()),
+        }
+    }
+}
+
+// "user@host.tld", or a URL with a scheme or a leading "www."
+fn is_address(word: &str) -> bool {
+    let email = word
+        .split_once('@')
+        .is_some_and(|(user, host)| !user.is_empty() && host.contains('.') && !host.contains('@'));
+    let url = word.contains("://") || word.starts_with("www.");
+    email || url
+}
+
+fn hyphenated(content: &str) -> Vec<&str> {
+    let mut words: Vec<(usize, &str)> = Vec::new();
+    for (start, word) in content.unicode_word_indices() {
+        if let Some(last) = words.last_mut() {
+            let end = last.0 + last.1.len();
+            if start == end + 1 && content.as_bytes()[end] == b'-' {
+                *last = (last.0, &content[last.0..start + word.len()]);
+                continue;
+            }
+        }
+        words.push((start, word));
+    }
+    words.into_iter().map(|(_, word)| word).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(content: &str) -> Vec<&str> {
+        Tokenizer::Unicode.split(content).collect()
+    }
+
+    #[test]
+    fn keeps_hyphenated_words() {
+        assert_eq!(split("a co-op, well-known"), ["a", "co-op", "well-known"]);
+    }
+
+    #[test]
+    fn keeps_emails_and_urls_whole() {
+        assert_eq!(
+            split("Mail <john@example.com>, see https://example.com/a?b=1."),
+            ["Mail", "john@example.com", "see", "https://example.com/a?b=1"]
+        );
+        assert_eq!(split("www.example.org"), ["www.example.org"]);
+    }
+
+    #[test]
+    fn splits_on_word_boundaries_otherwise() {
+        assert_eq!(split("\"word,\" said 3.5"), ["word", "said", "3.5"]);
+    }
+}
@@ -0,0 +1,77 @@
+// `--trace-tokens N`: the first N raw tokens as the tokenizer cut them,
+// with where they sit, what normalization changed, and the counted form or
+// the filter that dropped them, for working out why an expected word is
+// missing from the counts.
+
+use crate::redact::shown;
+use crate::token_stream::kind;
+use crate::{Config, checked, line_words, stem};
+
+pub struct Traced {
+    pub index: usize,
+    pub line: usize,
+    // Byte offset in the (preprocessed) text
+    pub offset: usize,
+    pub raw: String,
+    pub kind: &'static str,
+    pub changes: Vec<&'static str>,
+    // The counted form, or the step that rejected the token
+    pub outcome: Result<String, &'static str>,
+}
+
+pub fn trace(text: &str, config: &Config, n: usize) -> Vec<Traced> {
+    let with_lines = text.lines().enumerate().flat_map(|(line, content)| {
+        line_words(content, config).map(move |w| (line + 1, w))
+    });
+    with_lines
+        .take(n)
+        .enumerate()
+        .map(|(index, (line, (raw, numeral)))| {
+            let outcome = checked(raw, numeral, config);
+            let mut changes = Vec::new();
+            if let Ok(word) = &outcome {
+                let trimmed = raw.trim_matches(|c: char| !c.is_alphanumeric());
+                if trimmed.len() < raw.len() && !config.keep_punct && !config.chars {
+                    changes.push("punctuation trimmed");
+                }
+                if !config.case_sensitive && trimmed.to_lowercase() != trimmed {
+                    changes.push("lowercased");
+                }
+                if config.stem && stem::stem(word, config.language) != *word {
+                    changes.push("stemmed");
+                }
+            }
+            let outcome = outcome.map(|w| if config.stem { stem::stem(&w, config.language) } else { w });
+            Traced {
+                index: index + 1,
+                line,
+                offset: raw.as_ptr() as usize - text.as_ptr() as usize,
+                raw: raw.to_string(),
+                kind: kind(raw),
+                changes,
+                outcome,
+            }
+        })
+        .collect()
+}
+
+pub fn print(config: &Config, traced: &[Traced]) {
+    let redactor = config.redact.as_ref();
+    println!("\nToken trace:");
+    for t in traced {
+        let outcome = match &t.outcome {
+            Ok(word) if t.changes.is_empty() => format!("counted as \"{}\"", shown(redactor, word)),
+            Ok(word) => format!("counted as \"{}\" ({})", shown(redactor, word), t.changes.join(", ")),
+            Err(step) => format!("dropped by {}", step),
+        };
+        println!(
+            "  {:>5}. {:<20} line {}, byte {}, {}: {}",
+            t.index,
+            format!("\"{}\"", shown(redactor, &t.raw)),
+            t.line,
+            t.offset,
+            t.kind,
+            outcome
+        );
+    }
+}
@@ -0,0 +1,151 @@
+// `--input-format transcript`: "SPEAKER: line" transcripts. Labels are
+// stripped before the normal analysis and each speaker gets word counts,
+// turn lengths and the words that set them apart from everyone else.
+// Lines without a label continue the previous turn.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::redact::shown;
+use crate::report::fixed;
+use crate::{Config, tokens};
+
+const DISTINCTIVE_SHOWN: usize = 5;
+const MAX_LABEL_CHARS: usize = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputFormat {
+    Text,
+    Transcript,
+}
+
+impl InputFormat {
+    pub fn parse(name: &str) -> Option<InputFormat> {
+        match name {
+            "text" => Some(InputFormat::Text),
+            "transcript" => Some(InputFormat::Transcript),
+            _ => None,
+        }
+    }
+}
+
+pub struct Speaker {
+    pub name: String,
+    pub turns: Vec<usize>,
+    pub freq: HashMap<String, u32>,
+}
+
+pub struct Transcript {
+    pub speakers: Vec<Speaker>,
+}
+
+// "Interviewer:", "JOHN:", "Dr. Smith:" - short, starting with a capital
+fn label(line: &str) -> Option<(&str, &str)> {
+    let (name, rest) = line.split_once(':')?;
+    let name = name.trim();
+    let plausible = !name.is_empty()
+        && name.chars().count() <= MAX_LABEL_CHARS
+        && name.starts_with(char::is_uppercase)
+        && name.split_whitespace().count() <= 3
+        && !name.contains(['"', '(', '/']);
+    plausible.then_some((name, rest))
+}
+
+// Returns the text without labels (same line count) and the turns of each
+// speaker in order of first appearance
+pub fn parse(text: &str) -> (String, Vec<(String, String)>) {
+    let mut content = String::with_capacity(text.len());
+    let mut turns: Vec<(String, String)> = Vec::new();
+    for line in text.lines() {
+        let body = match label(line) {
+            Some((name, rest)) => {
+                turns.push((name.to_string(), String::new()));
+                rest
+            }
+            None => line,
+        };
+        if let Some((_, turn)) = turns.last_mut() {
+            turn.push_str(body);
+            turn.push('\n');
+        }
+        content.push_str(body);
+        content.push('\n');
+    }
+    (content, turns)
+}
+
+pub fn analyze(turns: &[(String, String)], config: &Config) -> Transcript {
+    let mut speakers: Vec<Speaker> = Vec::new();
+    for (name, turn) in turns {
+        let index = match speakers.iter().position(|s| s.name == *name) {
+            Some(i) => i,
+            None => {
+                speakers.push(Speaker {
+                    name: name.to_string(),
+                    turns: Vec::new(),
+                    freq: HashMap::new(),
+                });
+                speakers.len() - 1
+            }
+        };
+        let speaker = &mut speakers[index];
+        let mut words = 0;
+        for (_, word) in tokens(turn, config) {
+            *speaker.freq.entry(word).or_insert(0) += 1;
+            words += 1;
+        }
+        speaker.turns.push(words);
+    }
+    Transcript { speakers }
+}
+
+// Words a speaker uses far more than the others, by smoothed log ratio of
+// their rates; only words the speaker said at least twice
+fn distinctive<'a>(speaker: &'a Speaker, all: &HashMap<&str, u32>, total: u32) -> Vec<&'a str> {
+    let own_total: u32 = speaker.freq.values().sum();
+    let other_total = (total - own_total) as f64;
+    let mut scored: Vec<(&str, f64)> = speaker
+        .freq
+        .iter()
+        .filter(|&(_, &c)| c >= 2)
+        .map(|(w, &c)| {
+            let others = (all[w.as_str()] - c) as f64;
+            let own_rate = (c as f64 + 0.5) / (own_total as f64 + 1.0);
+            let other_rate = (others + 0.5) / (other_total + 1.0);
+            (w.as_str(), (own_rate / other_rate).ln())
+        })
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+    scored.into_iter().take(DISTINCTIVE_SHOWN).map(|(w, _)| w).collect()
+}
+
+pub fn print(config: &Config, transcript: &Transcript) {
+    let mut all: HashMap<&str, u32> = HashMap::new();
+    for speaker in &transcript.speakers {
+        for (w, &c) in &speaker.freq {
+            *all.entry(w.as_str()).or_insert(0) += c;
+        }
+    }
+    let total: u32 = all.values().sum();
+
+    let redactor = config.redact.as_ref();
+    println!("\nSpeakers:");
+    for speaker in &transcript.speakers {
+        let words: usize = speaker.turns.iter().sum();
+        let turns = speaker.turns.len();
+        let name = redactor.map_or(Cow::Borrowed(speaker.name.as_str()), |r| r.speaker(&speaker.name));
+        println!("  {}:", name);
+        println!("    Words: {}", words);
+        println!(
+            "    Turns: {} (average {} words, longest {})",
+            turns,
+            fixed(config, words as f64 / turns.max(1) as f64, 1),
+            speaker.turns.iter().max().unwrap_or(&0)
+        );
+        let distinctive: Vec<Cow<str>> = distinctive(speaker, &all, total)
+            .into_iter()
+            .map(|w| shown(redactor, w))
+            .collect();
+        println!("    Distinctive words: {}", distinctive.join(", "));
+    }
+}
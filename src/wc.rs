@@ -0,0 +1,219 @@
+// `--wc`: counts with `wc -lwmcL` semantics (UTF-8 locale), independent of
+// the word filters: newlines, words, characters, bytes, longest line.
+// Counting runs on the raw file bytes, so a BOM, invalid UTF-8 or a gzip
+// stream count exactly as `wc` counts them. Character classes come from the
+// Unicode tables of the crates, so characters assigned after the C library's
+// Unicode version may count differently.
+
+use std::fs;
+use std::io::{self, Read};
+
+use unicode_properties::{GeneralCategory, UnicodeGeneralCategory};
+use unicode_width::UnicodeWidthChar;
+
+use crate::{read_error, CliError, STDIN_PATH};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct WcCounts {
+    pub lines: usize,
+    pub words: usize,
+    pub chars: usize,
+    pub bytes: usize,
+    pub max_line_length: usize,
+}
+
+pub fn count(bytes: &[u8]) -> WcCounts {
+    let mut counts = WcCounts {
+        bytes: bytes.len(),
+        ..WcCounts::default()
+    };
+    let mut in_word = false;
+    let mut position = 0;
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        // Bytes that are not valid UTF-8 are neither characters nor separators
+        let Some((decoded, len)) = decode(rest) else {
+            rest = &rest[1..];
+            continue;
+        };
+        rest = &rest[len..];
+        counts.chars += 1;
+        // glibc also takes the old 5- and 6-byte forms beyond U+10FFFF: a
+        // character, but one without width that never starts a word
+        let Some(c) = decoded else {
+            continue;
+        };
+
+        // glibc's iswspace, plus the no-break spaces coreutils also splits
+        // on; only printable characters start a word
+        let space = c.is_whitespace() || matches!(c, '\u{a0}' | '\u{2007}' | '\u{202f}' | '\u{2060}');
+        if space {
+            in_word = false;
+        } else if !in_word && printable(c) {
+            in_word = true;
+            counts.words += 1;
+        }
+
+        match c {
+            '\n' => {
+                counts.lines += 1;
+                position = 0;
+            }
+            '\r' | '\x0c' => position = 0,
+            '\t' => position += 8 - position % 8,
+            // Display width as wcwidth: 2 for wide, 0 for combining marks
+            c if printable(c) => position += c.width().unwrap_or(0),
+            _ => {}
+        }
+        counts.max_line_length = counts.max_line_length.max(position);
+    }
+    counts
+}
+
+// glibc's iswprint: anything assigned but controls and line separators
+fn printable(c: char) -> bool {
+    !matches!(
+        c.general_category(),
+        GeneralCategory::Control
+            | GeneralCategory::Unassigned
+            | GeneralCategory::Surrogate
+            | GeneralCategory::LineSeparator
+            | GeneralCategory::ParagraphSeparator
+    )
+}
+
+// One character off the front of `bytes` as glibc's mbrtowc decodes it,
+// with its length; `None` inside for a code point past U+10FFFF
+fn decode(bytes: &[u8]) -> Option<(Option<char>, usize)> {
+    let lead = bytes[0];
+    let (len, min) = match lead {
+        0x00..=0x7f => return Some((Some(lead as char), 1)),
+        0xc2..=0xdf => (2, 0x80),
+        0xe0..=0xef => (3, 0x800),
+        0xf0..=0xf7 => (4, 0x1_0000),
+        0xf8..=0xfb => (5, 0x20_0000),
+        0xfc..=0xfd => (6, 0x400_0000),
+        _ => return None,
+    };
+    let tail = bytes.get(1..len)?;
+    if !tail.iter().all(|b| b & 0xc0 == 0x80) {
+        return None;
+    }
+    let value = tail
+        .iter()
+        .fold(u32::from(lead) & (0x7f >> len), |value, b| value << 6 | u32::from(b & 0x3f));
+    if value < min || (0xd800..=0xdfff).contains(&value) {
+        return None;
+    }
+    Some((char::from_u32(value), len))
+}
+
+fn read(path: &str) -> Result<Vec<u8>, CliError> {
+    let bytes = if path == STDIN_PATH {
+        let mut bytes = Vec::new();
+        io::stdin().lock().read_to_end(&mut bytes).map(|_| bytes)
+    } else {
+        fs::read(path)
+    };
+    bytes.map_err(|e| read_error(path, e))
+}
+
+// One row per input and a `total` row after several, in wc's layout:
+// columns as wide as the total byte count, at least 7 when reading stdin
+pub fn run(paths: &[String]) -> Result<(), CliError> {
+    let mut rows = Vec::new();
+    for path in paths {
+        rows.push((path.as_str(), count(&read(path)?)));
+    }
+    if rows.len() > 1 {
+        let mut total = WcCounts::default();
+        for (_, counts) in &rows {
+            total.lines += counts.lines;
+            total.words += counts.words;
+            total.chars += counts.chars;
+            total.bytes += counts.bytes;
+            total.max_line_length = total.max_line_length.max(counts.max_line_length);
+        }
+        rows.push(("total", total));
+    }
+    let bytes = rows.last().map_or(0, |(_, counts)| counts.bytes);
+    let mut width = bytes.to_string().len();
+    if paths.iter().any(|path| path == STDIN_PATH) {
+        width = width.max(7);
+    }
+    for (path, counts) in &rows {
+        print(path, counts, width);
+    }
+    Ok(())
+}
+
+fn print(path: &str, counts: &WcCounts, width: usize) {
+    // wc leaves the name off standard input
+    let name = if path == STDIN_PATH { String::new() } else { format!(" {path}") };
+    println!(
+        "{:>w$} {:>w$} {:>w$} {:>w$} {:>w$}{}",
+        counts.lines,
+        counts.words,
+        counts.chars,
+        counts.bytes,
+        counts.max_line_length,
+        name,
+        w = width
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wc(bytes: &[u8]) -> [usize; 5] {
+        let c = count(bytes);
+        [c.lines, c.words, c.chars, c.bytes, c.max_line_length]
+    }
+
+    // Expected values are GNU wc 9.1 output under C.UTF-8
+    #[test]
+    fn counts_a_bom_as_a_zero_width_character() {
+        assert_eq!(wc(b"\xef\xbb\xbfab\n"), [1, 1, 4, 6, 2]);
+    }
+
+    #[test]
+    fn skips_invalid_utf8_in_chars_but_not_bytes() {
+        assert_eq!(wc(b"ab\xffcd\n"), [1, 1, 5, 6, 4]);
+        assert_eq!(wc(b"a\xff b\n\xc3"), [1, 2, 4, 6, 3]);
+        assert_eq!(wc(b"\xff b\n"), [1, 1, 3, 4, 2]);
+        assert_eq!(wc(b"\xed\xa0\x80\xc0\x80\xe0\x80\x80"), [0, 0, 0, 8, 0]);
+    }
+
+    #[test]
+    fn counts_code_points_past_unicode_as_characters() {
+        assert_eq!(wc(b"\xf5\xa8\xa1\xbb"), [0, 0, 1, 4, 0]);
+        assert_eq!(wc(b"\xfc\x84\x80\x80\x80\x80"), [0, 0, 1, 6, 0]);
+        assert_eq!(wc(b"a\xf5\xa8\xa1\xbbb"), [0, 1, 3, 6, 2]);
+    }
+
+    #[test]
+    fn measures_lines_in_display_columns() {
+        let text = "日本語 x\tyz\n\u{200b}q\ncafe\u{301}\n";
+        assert_eq!(wc(text.as_bytes()), [3, 5, 18, 27, 18]);
+        assert_eq!(wc(b"abc\rd\n")[4], 3);
+    }
+
+    #[test]
+    fn unprintable_characters_do_not_start_words() {
+        assert_eq!(wc(b"\x01\n")[1], 0);
+        assert_eq!(wc("\u{378} \u{2028}\n".as_bytes())[1], 0);
+        assert_eq!(wc(b"a\x01b\x7fc\x0bd\n")[1..], [2, 8, 8, 4]);
+    }
+
+    #[test]
+    fn no_break_spaces_separate_words() {
+        assert_eq!(wc("x\u{a0}y z\n".as_bytes())[1], 3);
+        assert_eq!(wc("x\u{2060}y\n".as_bytes())[1], 2);
+    }
+
+    #[test]
+    fn counts_a_final_line_without_newline() {
+        assert_eq!(wc(b"one two\nthree"), [1, 3, 13, 13, 7]);
+    }
+}
@@ -0,0 +1,76 @@
+// `--weights FILE`: importance ranking. Each count is multiplied by the
+// word's weight from a `word,weight` CSV (a header row and `#` comment lines
+// are skipped); words the file does not list keep a weight of 1.
+
+use std::collections::HashMap;
+
+use crate::redact::shown;
+use crate::report::fixed;
+use crate::{AnalysisResult, Config};
+
+const SHOWN: usize = 10;
+
+pub struct Weights {
+    weights: HashMap<String, f64>,
+}
+
+impl Weights {
+    // The weights, or the 1-based number of the first malformed line
+    pub fn parse(list: &str) -> Result<Weights, usize> {
+        let mut weights = HashMap::new();
+        for (number, line) in list.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parsed = line
+                .rsplit_once(',')
+                .and_then(|(word, weight)| Some((word.trim(), weight.trim().parse::<f64>().ok()?)));
+            match parsed {
+                Some((word, weight)) if weight.is_finite() => {
+                    weights.insert(word.to_string(), weight);
+                }
+                // Header row
+                _ if number == 0 => {}
+                _ => return Err(number + 1),
+            }
+        }
+        Ok(Weights { weights })
+    }
+
+    // An exact entry first, then the lowercased one
+    fn of(&self, word: &str) -> f64 {
+        self.weights
+            .get(word)
+            .or_else(|| self.weights.get(&word.to_lowercase()))
+            .copied()
+            .unwrap_or(1.0)
+    }
+}
+
+// Words by count times weight, highest first, ties alphabetical
+pub fn ranked<'a>(analysis: &'a AnalysisResult, weights: &Weights) -> Vec<(&'a String, f64)> {
+    let mut scored: Vec<(&String, f64)> = analysis
+        .freq
+        .iter()
+        .map(|(word, &count)| (word, count as f64 * weights.of(word)))
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+    scored
+}
+
+pub fn print(config: &Config, analysis: &AnalysisResult, weights: &Weights) {
+    println!("\nWeighted importance:");
+    let shown_count = config.top.unwrap_or(SHOWN);
+    for (rank, (word, score)) in ranked(analysis, weights).into_iter().take(shown_count).enumerate() {
+        let count = analysis.freq[word];
+        println!(
+            "  {:>3}. {} {} ({} x {})",
+            rank + 1,
+            shown(config.redact.as_ref(), word),
+            fixed(config, score, 2),
+            count,
+            weights.of(word)
+        );
+    }
+}
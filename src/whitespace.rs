@@ -0,0 +1,54 @@
+// Whitespace and indentation statistics, mostly useful when the "text" is
+// source code or configuration.
+
+#[derive(Default)]
+pub struct WhitespaceStats {
+    pub tab_indented: usize,
+    pub space_indented: usize,
+    pub mixed_indented: usize,
+    pub trailing_whitespace: usize,
+    pub blank_lines: usize,
+    pub blank_runs: usize,
+    pub longest_blank_run: usize,
+}
+
+pub fn measure(text: &str) -> WhitespaceStats {
+    let mut stats = WhitespaceStats::default();
+    let mut run = 0;
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            stats.blank_lines += 1;
+            run += 1;
+            if run == 1 {
+                stats.blank_runs += 1;
+            }
+            stats.longest_blank_run = stats.longest_blank_run.max(run);
+            continue;
+        }
+        run = 0;
+
+        let indent: Vec<char> = line.chars().take_while(|c| *c == ' ' || *c == '\t').collect();
+        match (indent.contains(&'\t'), indent.contains(&' ')) {
+            (true, true) => stats.mixed_indented += 1,
+            (true, false) => stats.tab_indented += 1,
+            (false, true) => stats.space_indented += 1,
+            (false, false) => {}
+        }
+        if line.ends_with(char::is_whitespace) {
+            stats.trailing_whitespace += 1;
+        }
+    }
+    stats
+}
+
+pub fn print(stats: &WhitespaceStats) {
+    println!("\nWhitespace:");
+    println!("  Lines indented with tabs: {}", stats.tab_indented);
+    println!("  Lines indented with spaces: {}", stats.space_indented);
+    println!("  Lines with mixed indentation: {}", stats.mixed_indented);
+    println!("  Lines with trailing whitespace: {}", stats.trailing_whitespace);
+    println!(
+        "  Blank lines: {} in {} runs (longest {})",
+        stats.blank_lines, stats.blank_runs, stats.longest_blank_run
+    );
+}
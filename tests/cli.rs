@@ -0,0 +1,160 @@
+// End-to-end runs of the binary, for behavior that depends on how inputs
+// are read (standard input, several files) rather than on one module.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+fn run(args: &[&str], stdin: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_text_analyzer"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("starting the binary");
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{:?} exited with {}", args, output.status);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+// A fresh path under the target directory
+// Exit code and standard error of a run expected to fail
+fn fail(args: &[&str]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_text_analyzer"))
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .expect("starting the binary");
+    assert!(!output.status.success(), "{:?} succeeded", args);
+    (output.status.code().unwrap_or(-1), String::from_utf8(output.stderr).unwrap())
+}
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("cli");
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+#[test]
+fn export_idf_reads_stdin_once() {
+    let out = scratch("stdin-idf.csv");
+    run(&["-", "--export-idf", out.to_str().unwrap()], "the cat saw the dog\n");
+    let table = std::fs::read_to_string(&out).unwrap();
+    assert!(table.contains("# documents: 1\n"), "{}", table);
+    assert!(table.contains("\nthe,1,0.000000\n"), "{}", table);
+    assert!(table.contains("\ncat,1,0.000000\n"), "{}", table);
+}
+
+#[test]
+fn suggest_stopwords_from_stdin() {
+    let report = run(&["-", "--suggest-stopwords", "2"], "the cat and the dog and the bird\n");
+    let section = report.split("Suggested stopwords:\n").nth(1).expect("suggestions section");
+    let rows: Vec<&str> = section.lines().take(2).collect();
+    assert!(rows[0].trim_start().starts_with("the "), "{:?}", rows);
+    assert!(rows[0].ends_with("in 1/1 documents, count 3"), "{:?}", rows);
+    assert!(rows[1].ends_with("in 1/1 documents, count 2"), "{:?}", rows);
+}
+
+#[test]
+fn wc_prints_a_row_per_file_and_a_total() {
+    let bom = scratch("wc-bom.txt");
+    std::fs::write(&bom, b"\xef\xbb\xbfab\n").unwrap();
+    let two = scratch("wc-two.txt");
+    std::fs::write(&two, "one two\nthree").unwrap();
+    let (bom, two) = (bom.to_str().unwrap(), two.to_str().unwrap());
+    // `wc -lwmcL` on the same files
+    let expected = format!(" 1  1  4  6  2 {bom}\n 1  3 13 13  7 {two}\n 2  4 17 19  7 total\n");
+    assert_eq!(run(&[bom, two, "--wc"], ""), expected);
+    assert_eq!(run(&["-", "--wc"], "x y\n"), "      1       2       4       4       3\n");
+}
+
+#[test]
+fn directories_skip_binary_files_and_list_what_they_read() {
+    let dir = scratch("binary-dir");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.txt"), "one two\n").unwrap();
+    std::fs::write(dir.join("b.bin"), b"bin\0ary").unwrap();
+    std::fs::write(dir.join("c.txt"), "b\0i\0n\0").unwrap();
+    let report = run(&[dir.to_str().unwrap(), "--recursive"], "");
+    let (a, c) = (dir.join("a.txt"), dir.join("c.txt"));
+    assert!(report.contains(&format!("Files: {}, {}\n", a.display(), c.display())), "{}", report);
+    assert!(report.contains("Total word count: 3\n"), "{}", report);
+}
+
+#[test]
+fn empty_files_count_as_zero_among_several() {
+    let (full, empty) = (scratch("full.txt"), scratch("empty.txt"));
+    std::fs::write(&full, "one two\n").unwrap();
+    std::fs::write(&empty, "  \n").unwrap();
+    let (full, empty) = (full.to_str().unwrap(), empty.to_str().unwrap());
+    for extra in [&[][..], &["--whitespace"][..]] {
+        let report = run(&[&[full, empty][..], extra].concat(), "");
+        assert!(report.contains(&format!("Empty (counted as zero): {}\n", empty)), "{}", report);
+        assert!(report.contains("Total word count: 2\n"), "{}", report);
+    }
+    let alone = Command::new(env!("CARGO_BIN_EXE_text_analyzer")).arg(empty).output().unwrap();
+    assert_eq!(alone.status.code(), Some(7));
+}
+
+#[test]
+fn table_puts_files_in_columns() {
+    let (a, b) = (scratch("table-a.txt"), scratch("table-b.txt"));
+    std::fs::write(&a, "the cat sat on the mat\n").unwrap();
+    std::fs::write(&b, "dogs bark\n").unwrap();
+    let table = run(&[a.to_str().unwrap(), b.to_str().unwrap(), "--format", "table"], "");
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines[0].ends_with("table-b.txt"), "{}", table);
+    assert_eq!(lines[1].split_whitespace().collect::<Vec<_>>(), ["Total", "words", "6", "2"]);
+    assert_eq!(lines[3].split_whitespace().collect::<Vec<_>>(), ["Type-token", "ratio", "0.833", "1.000"]);
+    assert!(lines[6].starts_with("Grade level"), "{}", table);
+}
+
+#[test]
+fn dedup_docs_leaves_near_copies_out_of_the_counts() {
+    let (a, b, c) = (scratch("dedup-a.txt"), scratch("dedup-b.txt"), scratch("dedup-c.txt"));
+    let text = "the quick brown fox jumps over the lazy dog while the small cat sleeps on the warm mat\n".repeat(4);
+    std::fs::write(&a, &text).unwrap();
+    std::fs::write(&b, text.clone() + "and then it rained\n").unwrap();
+    std::fs::write(&c, "dogs bark\n").unwrap();
+    let paths = [a.to_str().unwrap(), b.to_str().unwrap(), c.to_str().unwrap()];
+    let report = run(&[paths[0], paths[1], paths[2], "--dedup-docs", "0.8"], "");
+    assert!(report.contains(&format!("  {}: ", paths[1])), "{}", report);
+    assert!(report.contains(&format!(" similar to {}\n", paths[0])), "{}", report);
+    assert!(report.contains("Total word count: 74\n"), "{}", report);
+}
+
+#[test]
+fn mixed_directories_report_each_extension() {
+    let dir = scratch("mixed");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.md"), "# Notes\nthe code and the list\n").unwrap();
+    std::fs::write(dir.join("b.txt"), "the cat\n").unwrap();
+    std::fs::write(dir.join("c.txt"), "a dog\n").unwrap();
+    let report = run(&[dir.to_str().unwrap(), "--recursive"], "");
+    let section = &report[report.find("By extension:\n").expect("an extension section")..];
+    assert!(section.contains("  .md (1 file)\n    Total words: 6\n"), "{}", section);
+    assert!(section.contains("  .txt (2 files)\n    Total words: 4\n"), "{}", section);
+
+    let named = run(&[dir.join("a.md").to_str().unwrap(), dir.join("b.txt").to_str().unwrap()], "");
+    assert!(!named.contains("By extension:"), "{}", named);
+}
+
+#[test]
+fn words_per_paragraph_counts_filtered_words() {
+    let report = run(&["-", "--stopwords", "english"], "the cat and the dog and a bird\n\nthe end of it all\n");
+    assert!(report.contains("Total word count: 4\n"), "{}", report);
+    assert!(report.contains("Paragraphs: 2 (2.0 words per paragraph)"), "{}", report);
+}
+
+#[test]
+fn stopwords_without_a_list_is_a_missing_value() {
+    assert_eq!(fail(&["-", "--stopwords"]), (29, "Error: --stopwords needs a value\n".to_string()));
+}
+
+#[test]
+fn word_filters_without_a_value_are_refused() {
+    for flag in ["--ends-with", "--contains", "--exclude"] {
+        assert_eq!(fail(&["-", flag]), (29, format!("Error: {} needs a value\n", flag)));
+    }
+}